@@ -0,0 +1,87 @@
+/*!
+Cellular automata on small grids.
+
+The state of a grid of at most 64 cells is a bitmask with bit `y * width + x` set for every live cell.
+Every cell's neighbourhood is precomputed as a mask, so counting live neighbours is a single `count_ones`.
+*/
+
+use std::collections::HashSet;
+
+/// Returns if the cell is alive in the next generation given if it's alive now and its number of live neighbours.
+pub type Rule = fn(bool, u32) -> bool;
+
+#[derive(Clone, Debug)]
+pub struct Automaton {
+	pub width: i32,
+	pub height: i32,
+	/// Neighbourhood mask of every cell.
+	pub neighbours: Vec<u64>,
+	pub rule: Rule,
+}
+impl Automaton {
+	/// Creates the automaton, the neighbourhood is given as offsets and doesn't wrap around the edges.
+	pub fn new(width: i32, height: i32, offsets: &[(i32, i32)], rule: Rule) -> Automaton {
+		assert!(width * height <= 64, "grid too large for a bitmask");
+		let mut neighbours = Vec::new();
+		for y in 0..height {
+			for x in 0..width {
+				let mask = offsets.iter()
+					.map(|&(dx, dy)| (x + dx, y + dy))
+					.filter(|&(nx, ny)| nx >= 0 && nx < width && ny >= 0 && ny < height)
+					.fold(0, |mask, (nx, ny)| mask | 1 << (ny * width + nx));
+				neighbours.push(mask);
+			}
+		}
+		Automaton { width, height, neighbours, rule }
+	}
+	/// Horizontally and vertically adjacent cells.
+	pub fn von_neumann(width: i32, height: i32, rule: Rule) -> Automaton {
+		Automaton::new(width, height, &[(0, -1), (0, 1), (-1, 0), (1, 0)], rule)
+	}
+	/// Parses the grid, `#` is a live cell.
+	pub fn parse(&self, s: &str) -> u64 {
+		s.lines().map(str::trim).filter(|line| !line.is_empty()).flat_map(|line| line.bytes())
+			.enumerate()
+			.filter(|&(_, cell)| cell == b'#')
+			.fold(0, |state, (index, _)| state | 1 << index)
+	}
+	pub fn render(&self, state: u64) -> String {
+		let mut s = String::new();
+		for y in 0..self.height {
+			for x in 0..self.width {
+				s.push(if state & 1 << (y * self.width + x) != 0 { '#' } else { '.' });
+			}
+			s.push('\n');
+		}
+		s
+	}
+	/// Applies the rule to the cell given the number of live neighbours.
+	pub fn cell(&self, state: u64, index: usize, count: u32) -> bool {
+		(self.rule)(state & 1 << index != 0, count)
+	}
+	pub fn step(&self, state: u64) -> u64 {
+		self.neighbours.iter().enumerate()
+			.filter(|&(index, &mask)| self.cell(state, index, (state & mask).count_ones()))
+			.fold(0, |next, (index, _)| next | 1 << index)
+	}
+	/// Returns the first state which appears twice.
+	pub fn first_repeat(&self, mut state: u64) -> u64 {
+		let mut seen = HashSet::new();
+		while seen.insert(state) {
+			state = self.step(state);
+		}
+		state
+	}
+}
+
+#[test]
+fn test_life() {
+	// Conway's game of life blinker on a 5x5 grid
+	let offsets = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+	let life = Automaton::new(5, 5, &offsets, |alive, count| count == 3 || alive && count == 2);
+	let blinker = life.parse(".....\n..#..\n..#..\n..#..\n.....\n");
+	let next = life.step(blinker);
+	assert_eq!(life.render(next), ".....\n.....\n.###.\n.....\n.....\n");
+	assert_eq!(life.step(next), blinker);
+	assert_eq!(life.first_repeat(blinker), blinker);
+}
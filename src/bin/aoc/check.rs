@@ -0,0 +1,92 @@
+/*!
+Regression checks of the answers to the actual inputs against the expected answers in `answers/dayX.txt`.
+
+The answers file has the answer to part one on its first line and to part two on its second line, an empty line has no expected answer.
+Image answers are stored as the letters they show.
+*/
+
+use std::{fs, io};
+use std::path::Path;
+use adventofcode2019::solution::{Entry, Record};
+
+/// Reads the puzzle input, missing and blank inputs are errors explaining why the day is skipped.
+pub fn read_input(path: &Path) -> Result<String, String> {
+	match fs::read_to_string(path) {
+		Ok(text) if text.trim().is_empty() => Err(format!("empty input at `{}`", path.display())),
+		Ok(text) => Ok(text),
+		Err(err) if err.kind() == io::ErrorKind::NotFound => Err(format!("no input at `{}`", path.display())),
+		Err(err) => Err(format!("error reading `{}`: {}", path.display(), err)),
+	}
+}
+
+/// The answer as it's stored.
+fn stored(record: &Record) -> Option<String> {
+	record.ocr().or_else(|| record.answer.clone())
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+	Pass,
+	Fail { expected: String },
+	/// There's no expected answer yet.
+	New,
+	/// There's neither an answer nor an expected one.
+	Unsolved,
+}
+
+fn parse_expected(text: &str) -> [Option<String>; 2] {
+	let mut lines = text.lines().map(|line| Some(line.trim()).filter(|line| !line.is_empty()).map(str::to_string));
+	[lines.next().flatten(), lines.next().flatten()]
+}
+
+fn status(record: &Record, expected: Option<&str>) -> Status {
+	match (stored(record), expected) {
+		(None, None) => Status::Unsolved,
+		(Some(_), None) => Status::New,
+		(Some(answer), Some(expected)) if answer == expected => Status::Pass,
+		(_, Some(expected)) => Status::Fail { expected: expected.to_string() },
+	}
+}
+
+/// Solves both parts of the day and compares them with the expected answers, or returns why the day is skipped.
+pub fn check(entry: &Entry, inputs: &Path, answers: &Path) -> Result<Vec<(Record, Status)>, String> {
+	let input = read_input(&inputs.join(format!("day{}.txt", entry.day)))?;
+	let expected = fs::read_to_string(answers.join(format!("day{}.txt", entry.day))).map(|text| parse_expected(&text)).unwrap_or_default();
+	Ok((1..=2).map(|part| {
+		let record = entry.solve(part, &input);
+		let status = status(&record, expected[part - 1].as_deref());
+		(record, status)
+	}).collect())
+}
+
+/// Stores the answers as the expected ones, image answers which can't be read are left out.
+pub fn bless(day: u32, records: &[Record], answers: &Path) -> io::Result<()> {
+	let text = records.iter()
+		.map(|record| stored(record).filter(|answer| !answer.contains('\n')).unwrap_or_default() + "\n")
+		.collect::<String>();
+	fs::create_dir_all(answers)?;
+	fs::write(answers.join(format!("day{}.txt", day)), text)
+}
+
+#[test]
+fn test_check() {
+	use std::time::Duration;
+	let record = |answer: Option<&str>| Record { day: 1, part: 1, answer: answer.map(str::to_string), duration: Duration::default() };
+	assert_eq!(parse_expected("12\n\n"), [Some("12".to_string()), None]);
+	assert_eq!(parse_expected(""), [None, None]);
+	assert_eq!(status(&record(Some("12")), Some("12")), Status::Pass);
+	assert_eq!(status(&record(Some("13")), Some("12")), Status::Fail { expected: "12".to_string() });
+	assert_eq!(status(&record(Some("12")), None), Status::New);
+	assert_eq!(status(&record(None), Some("12")), Status::Fail { expected: "12".to_string() });
+	assert_eq!(status(&record(None), None), Status::Unsolved);
+	assert_eq!(status(&record(Some(".##.\n#..#\n#..#\n####\n#..#\n#..#\n")), Some("A")), Status::Pass);
+
+	let dir = std::env::temp_dir().join(format!("aoc-check-{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	fs::write(dir.join("blank.txt"), " \n").unwrap();
+	assert_eq!(read_input(&dir.join("blank.txt")), Err(format!("empty input at `{}`", dir.join("blank.txt").display())));
+	assert_eq!(read_input(&dir.join("missing.txt")), Err(format!("no input at `{}`", dir.join("missing.txt").display())));
+	bless(7, &[record(Some("12")), record(None)], &dir).unwrap();
+	assert_eq!(fs::read_to_string(dir.join("day7.txt")).unwrap(), "12\n\n");
+	fs::remove_dir_all(&dir).unwrap();
+}
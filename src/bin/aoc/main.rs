@@ -0,0 +1,153 @@
+/*!
+Runs the solutions of every day.
+
+Usage: `cargo run --release --bin aoc -- run <day|all> [--input <path>] [--part <1|2>] [--json]`
+or `cargo run --release --bin aoc -- test <day|all> [--bless]`.
+
+The input of day X is read from `input/dayX.txt` unless given with `--input`, days without an input file or with an empty one are skipped when running all of them.
+With `--json` every part is printed as a JSON object `{"day", "part", "answer", "duration_ns"}` on its own line, image answers get an `ocr` field with the letters they show.
+
+Testing compares the answers with the expected ones in `answers/dayX.txt` and fails on any mismatch, `--bless` stores the current answers as the expected ones.
+*/
+
+mod check;
+
+use std::{env, process};
+use std::path::Path;
+use adventofcode2019::days;
+use adventofcode2019::solution::{Entry, Record};
+use check::Status;
+
+const USAGE: &str = "usage: aoc run <day|all> [--input <path>] [--part <1|2>] [--json] | aoc test <day|all> [--bless]";
+
+fn main() {
+	let mut args = env::args().skip(1);
+	let command = args.next().expect(USAGE);
+	let which = args.next().expect(USAGE);
+	let (mut input, mut parts, mut json, mut bless) = (None, vec![1, 2], false, false);
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--bless" => bless = true,
+			"--input" => input = Some(args.next().expect("missing input file")),
+			"--part" => parts = match args.next().as_deref() {
+				Some("1") => vec![1],
+				Some("2") => vec![2],
+				_ => panic!("the part must be 1 or 2"),
+			},
+			"--json" => json = true,
+			_ => panic!("unknown argument: {}", arg),
+		}
+	}
+
+	let entries = days::all();
+	let selected = if which == "all" {
+		assert!(input.is_none(), "--input needs a single day");
+		entries
+	}
+	else {
+		let day = which.parse::<u32>().expect(USAGE);
+		entries.into_iter().filter(|entry| entry.day == day).collect()
+	};
+	if selected.is_empty() {
+		panic!("day {} has no solution", which);
+	}
+
+	match command.as_str() {
+		"run" => run(&selected, input, &parts, json, which == "all"),
+		"test" => if !test(&selected, bless) {
+			process::exit(1);
+		},
+		_ => panic!("{}", USAGE),
+	}
+}
+
+fn run(selected: &[Entry], input: Option<String>, parts: &[usize], json: bool, all: bool) {
+	for entry in selected {
+		let path = input.clone().unwrap_or_else(|| format!("input/day{}.txt", entry.day));
+		let text = match check::read_input(Path::new(&path)) {
+			Ok(text) => text,
+			Err(reason) if all && json => {
+				eprintln!("Skipped day {}, {}.", entry.day, reason);
+				continue;
+			},
+			Err(reason) if all => {
+				print!("Day {}\n======\n\nSkipped, {}.\n\n", entry.day, reason);
+				continue;
+			},
+			Err(reason) => panic!("{}", reason),
+		};
+		let records = parts.iter().map(|&part| entry.solve(part, &text));
+		if json {
+			records.for_each(|record| println!("{}", record.to_json()));
+		}
+		else {
+			print!("Day {}\n======\n\n", entry.day);
+			records.for_each(|record| print_record(&record));
+		}
+	}
+}
+
+fn print_record(record: &Record) {
+	let name = if record.part == 1 { "Part One" } else { "Part Two" };
+	match (&record.answer, record.ocr()) {
+		(Some(answer), Some(letters)) => print!("{} in {:?}: `{}`\n\n```\n{}```\n\n", name, record.duration, letters, answer),
+		(Some(answer), None) if answer.contains('\n') => print!("{} in {:?}:\n\n```\n{}```\n\n", name, record.duration, answer),
+		(Some(answer), None) => print!("{} in {:?}: `{}`\n\n", name, record.duration, answer),
+		(None, _) => print!("{}: not solved\n\n", name),
+	}
+}
+
+/// Checks the answers of every day, returns if they all match.
+fn test(selected: &[Entry], bless: bool) -> bool {
+	let (answers, inputs) = (Path::new("answers"), Path::new("input"));
+	let (mut passed, mut failed, mut new, mut unsolved, mut skipped) = (0, 0, 0, 0, 0);
+	for entry in selected {
+		let results = match check::check(entry, inputs, answers) {
+			Ok(results) => results,
+			Err(reason) => {
+				println!("Day {}: skipped, {}.", entry.day, reason);
+				skipped += 1;
+				continue;
+			},
+		};
+		for (record, status) in &results {
+			let answer = record.ocr().or_else(|| record.answer.clone()).unwrap_or_default();
+			match status {
+				Status::Pass => {
+					println!("Day {} part {} in {:?}: ok `{}`", record.day, record.part, record.duration, answer);
+					passed += 1;
+				},
+				Status::Fail { expected } => {
+					let got = if record.answer.is_some() { format!("`{}`", answer) } else { "no answer".to_string() };
+					println!("Day {} part {} in {:?}: FAILED, expected `{}` but got {}", record.day, record.part, record.duration, expected, got);
+					failed += 1;
+				},
+				Status::New => {
+					println!("Day {} part {} in {:?}: new `{}`", record.day, record.part, record.duration, answer);
+					new += 1;
+				},
+				Status::Unsolved => {
+					println!("Day {} part {}: not solved", record.day, record.part);
+					unsolved += 1;
+				},
+			}
+		}
+		if bless {
+			let records = results.into_iter().map(|(record, _)| record).collect::<Vec<_>>();
+			check::bless(entry.day, &records, answers).expect("error storing the answers");
+		}
+	}
+	println!("\n{} passed, {} failed, {} new, {} not solved, {} skipped.", passed, failed, new, unsolved, skipped);
+	failed == 0
+}
+
+#[test]
+fn test_answers() {
+	for entry in days::all() {
+		if let Ok(results) = check::check(&entry, Path::new("input"), Path::new("answers")) {
+			for (record, status) in results {
+				assert!(!matches!(status, Status::Fail { .. }), "day {} part {}: {:?} but got {:?}", record.day, record.part, status, record.answer);
+			}
+		}
+	}
+}
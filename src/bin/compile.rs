@@ -0,0 +1,44 @@
+/*!
+Compiles a program to Intcode.
+
+Usage: `cargo run --bin compile [input] < program.txt`.
+
+Prints the compiled Intcode program. With an argument the program is run instead, the argument being a comma separated list of input values.
+*/
+
+use std::{env, io, process};
+use std::io::prelude::*;
+use adventofcode2019::intcode::{compiler, execute};
+
+fn main() {
+	let mut source = String::new();
+	let _ = io::stdin().read_to_string(&mut source).expect("error reading input");
+
+	let program = match compiler::compile(&source) {
+		Ok(program) => program,
+		Err(err) => {
+			eprintln!("error: {}", err);
+			process::exit(1);
+		},
+	};
+
+	match env::args().nth(1) {
+		Some(arg) => {
+			let input = arg.split(",").filter(|s| !s.is_empty()).map(|value| value.parse().expect("error parsing input")).collect::<Vec<i64>>();
+			match execute(&program, &input) {
+				Ok(output) => {
+					let output = output.iter().map(|value| value.to_string()).collect::<Vec<_>>();
+					println!("{}", output.join(","));
+				},
+				Err(fault) => {
+					eprintln!("error: {}", fault);
+					process::exit(1);
+				},
+			}
+		},
+		None => {
+			let program = program.iter().map(|value| value.to_string()).collect::<Vec<_>>();
+			println!("{}", program.join(","));
+		},
+	}
+}
@@ -0,0 +1,28 @@
+/*!
+Runs the Intcode conformance suite on all built-in backends.
+
+Usage: `cargo run --bin conformance`.
+*/
+
+use std::process;
+use adventofcode2019::intcode::{backend, conformance};
+
+fn main() {
+	print!("Conformance\n===========\n\n");
+	let mut failed = false;
+	for backend in &backend::backends() {
+		match conformance::check(&**backend) {
+			Ok(count) => print!("The backend `{}` passes `{}` cases.\n\n", backend.name(), count),
+			Err(failures) => {
+				print!("The backend `{}` fails `{}` cases:\n\n", backend.name(), failures.len());
+				for failure in &failures {
+					println!("{}", failure);
+				}
+				failed = true;
+			},
+		}
+	}
+	if failed {
+		process::exit(1);
+	}
+}
@@ -0,0 +1,3 @@
+fn main() {
+	adventofcode2019::days::day21::main();
+}
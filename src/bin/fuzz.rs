@@ -0,0 +1,34 @@
+/*!
+Differential fuzzing of the Intcode backends.
+
+Usage: `cargo run --release --bin fuzz [seed] [iterations]`.
+*/
+
+use std::env;
+use std::process;
+use adventofcode2019::intcode::{backend, fuzz};
+
+fn main() {
+	let mut args = env::args().skip(1);
+	let mut config = fuzz::Config::default();
+	if let Some(seed) = args.next() {
+		config.seed = seed.parse().expect("error parsing seed");
+	}
+	if let Some(iterations) = args.next() {
+		config.iterations = iterations.parse().expect("error parsing iterations");
+	}
+
+	let backends = backend::backends();
+	let backends = backends.iter().map(|backend| &**backend).collect::<Vec<_>>();
+	let names = backends.iter().map(|backend| backend.name()).collect::<Vec<_>>();
+
+	match fuzz::fuzz(&backends, &config) {
+		Ok(count) => {
+			print!("Fuzzing\n=======\n\nThe backends `{}` agree on `{}` programs with seed `{}`.\n\n", names.join("`, `"), count, config.seed);
+		},
+		Err(divergence) => {
+			print!("Fuzzing\n=======\n\nThe backends diverge with seed `{}` on the program:\n\n{}\n", config.seed, divergence);
+			process::exit(1);
+		},
+	}
+}
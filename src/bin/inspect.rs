@@ -0,0 +1,88 @@
+/*!
+Inspects the memory of an Intcode program.
+
+Usage:
+* `cargo run --bin inspect <program> dump [start] [end]`: table of the memory with disassembled instructions.
+* `cargo run --bin inspect <program> search <values>`: addresses of a comma separated sequence of values.
+* `cargo run --bin inspect <program> diff <other>`: differences to another program.
+* `cargo run --bin inspect <program> run [input] [--smc]`: runs the program on the comma separated input and shows the changed memory,
+  with `--smc` also the summary of the writes into executed code.
+*/
+
+use std::{env, fs, process};
+use adventofcode2019::intcode::{inspect, load, Intcode, State};
+
+fn read(path: &str) -> Vec<i64> {
+	let bytes = fs::read(path).expect("error reading program");
+	load::load(&bytes).unwrap_or_else(|err| panic!("error loading program: {}", err))
+}
+
+fn values(arg: Option<String>) -> Vec<i64> {
+	arg.unwrap_or_default().split(",").filter(|s| !s.is_empty()).map(|value| value.trim().parse().expect("error parsing value")).collect()
+}
+
+fn main() {
+	let mut args = env::args().skip(1);
+	let usage = "usage: inspect <program> dump [start] [end] | search <values> | diff <other> | run [input] [--smc]";
+	let program = read(&args.next().expect(usage));
+	let command = args.next().unwrap_or_else(|| "dump".to_string());
+
+	match command.as_str() {
+		"dump" => {
+			let mut bound = |default| args.next().map(|arg| arg.parse().expect("error parsing address")).unwrap_or(default);
+			let start = bound(0);
+			let end = bound(program.len());
+			print!("{}", inspect::Table::new(&program, start, end));
+		},
+		"search" => {
+			let needle = values(args.next());
+			let found = inspect::search(&program, &needle);
+			println!("Found `{:?}` at `{}` addresses: `{:?}`.", needle, found.len(), found);
+		},
+		"diff" => {
+			let other = read(&args.next().expect(usage));
+			print!("{}", inspect::diff(&program, &other));
+		},
+		"run" => {
+			let (flags, input): (Vec<String>, Vec<String>) = args.partition(|arg| arg.starts_with("--"));
+			let smc = match flags.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+				[] => false,
+				["--smc"] => true,
+				_ => {
+					eprintln!("{}", usage);
+					process::exit(1);
+				},
+			};
+			let mut vm = Intcode::new(&program);
+			if smc {
+				vm.track_code();
+			}
+			vm.input.extend(values(input.into_iter().next()));
+			let mut output = Vec::new();
+			loop {
+				match vm.run() {
+					Ok(State::Running) => (),
+					Ok(State::Output(value)) => output.push(value),
+					Ok(State::Halted) => break,
+					Ok(State::Input) => {
+						eprintln!("error: missing input at ip {}", vm.ip);
+						process::exit(1);
+					},
+					Err(fault) => {
+						eprintln!("error: {}", fault);
+						process::exit(1);
+					},
+				}
+			}
+			println!("Output: `{:?}`.", output);
+			print!("{}", inspect::diff(&program, &vm.mem));
+			if let Some(smc) = &vm.smc {
+				print!("{}", smc);
+			}
+		},
+		_ => {
+			eprintln!("{}", usage);
+			process::exit(1);
+		},
+	}
+}
@@ -0,0 +1,35 @@
+/*!
+Peephole optimizer for Intcode programs.
+
+Usage: `cargo run --bin optimize [input]... < input/dayX.txt`.
+
+Every argument is a comma separated list of input values.
+The program is left unchanged if it writes into its executed code on any of them, otherwise the optimized program is validated against the original on them.
+*/
+
+use std::{env, process};
+use adventofcode2019::intcode::{load, opt};
+
+fn main() {
+	let program = load::stdin::<i64>();
+
+	let inputs = env::args().skip(1)
+		.map(|arg| arg.split(",").filter(|s| !s.is_empty()).map(|value| value.parse().expect("error parsing input")).collect())
+		.collect::<Vec<Vec<i64>>>();
+
+	let (optimized, report) = opt::optimize_checked(&program, &inputs, 10000000);
+	print!("Optimizer\n=========\n\n{}\n\n", report);
+
+	match opt::validate(&program, &optimized, &inputs, 10000000) {
+		Ok(validation) => {
+			print!("Validated on `{}` inputs, executed `{}` instructions before and `{}` after.\n\n", validation.runs, validation.before, validation.after);
+		},
+		Err(input) => {
+			print!("Validation failed on input `{:?}`.\n\n", input);
+			process::exit(1);
+		},
+	}
+
+	let optimized = optimized.iter().map(|value| value.to_string()).collect::<Vec<_>>();
+	print!("```\n{}\n```\n", optimized.join(","));
+}
@@ -0,0 +1,92 @@
+/*!
+Runs an interactive Intcode session, recording or replaying its input.
+
+Usage: `cargo run --bin session <program> [--record <log>] [--replay <log>] [--live] [--ascii]`.
+
+Live input is read from stdin, one value per line, invalid lines are reported and skipped.
+Replaying a log stops where the log ends unless `--live` continues with live input.
+In ASCII mode every input line is sent as characters followed by a newline and output is printed as text.
+Every input is appended to the recorded log as soon as it's consumed, so the log survives a crash or an interrupted session.
+*/
+
+use std::{env, fs, io, process};
+use std::fs::File;
+use std::collections::VecDeque;
+use std::io::prelude::*;
+use adventofcode2019::intcode::load;
+use adventofcode2019::intcode::replay::{Log, Session, Stop};
+
+fn main() {
+	let mut args = env::args().skip(1);
+	let path = args.next().expect("usage: session <program> [--record <log>] [--replay <log>] [--live] [--ascii]");
+	let (mut record, mut replay, mut live, mut ascii) = (None, None, false, false);
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--record" => record = Some(args.next().expect("missing log file")),
+			"--replay" => replay = Some(args.next().expect("missing log file")),
+			"--live" => live = true,
+			"--ascii" => ascii = true,
+			_ => panic!("unknown argument: {}", arg),
+		}
+	}
+
+	let bytes = fs::read(&path).expect("error reading program");
+	let program = load::load(&bytes).unwrap_or_else(|err| panic!("error loading program: {}", err));
+
+	let mut session = Session::new(&program);
+	match &replay {
+		Some(replay) => {
+			let log = fs::read_to_string(replay).expect("error reading log");
+			session = session.replay(&Log::parse(&log).unwrap_or_else(|err| panic!("{}", err)));
+		},
+		None => live = true,
+	}
+	if live {
+		let stdin = io::stdin();
+		let mut lines = stdin.lock().lines();
+		let mut pending = VecDeque::new();
+		session = session.live(move || {
+			while pending.is_empty() {
+				let line = lines.next()?.expect("error reading input");
+				if ascii {
+					pending.extend(line.bytes().map(i64::from));
+					pending.push_back(10);
+				}
+				else if !line.trim().is_empty() {
+					match line.trim().parse() {
+						Ok(value) => pending.push_back(value),
+						Err(_) => eprintln!("Invalid input `{}`, enter an integer.", line.trim()),
+					}
+				}
+			}
+			pending.pop_front()
+		});
+	}
+	if let Some(record) = &record {
+		let mut file = File::create(record).expect("error creating log");
+		writeln!(file, "# steps value").expect("error writing log");
+		session = session.recorder(move |record| {
+			writeln!(file, "{}", record).expect("error writing log");
+			file.flush().expect("error writing log");
+		});
+	}
+
+	let result = session.run(&mut |value| {
+		if ascii && (0..128).contains(&value) {
+			print!("{}", value as u8 as char);
+		}
+		else {
+			println!("{}", value);
+		}
+	});
+
+	match result {
+		Ok(Stop::Halted) => (),
+		Ok(Stop::LogEnd) => eprintln!("Log ended after `{}` inputs at step `{}`.", session.log.records.len(), session.vm.steps),
+		Ok(Stop::InputClosed) => eprintln!("Input closed at step `{}`.", session.vm.steps),
+		Err(err) => {
+			eprintln!("error: {}", err);
+			process::exit(1);
+		},
+	}
+}
@@ -0,0 +1,49 @@
+use std::io;
+use std::io::prelude::*;
+use crate::solution::Solution;
+
+fn calculate_recursive_fuel(mass: f64) -> f64 {
+	let fuel = (mass / 3.0).floor() - 2.0;
+	if fuel <= 0.0 {
+		return 0.0;
+	}
+	fuel + calculate_recursive_fuel(fuel)
+}
+
+pub fn main() {
+	let mut input = String::new();
+	let _ = io::stdin().read_to_string(&mut input).expect("error reading input");
+
+	let mass = parse_mass(&input);
+
+	let total_fuel1 = part_one(&mass);
+
+	println!("Part One\n========\n\nThe sum of the fuel requirements is `{}`.\n", total_fuel1);
+
+	let total_fuel2 = part_two(&mass);
+
+	println!("Part Two\n========\n\nThe sum of the fuel requirements is `{}`.\n", total_fuel2);
+}
+
+fn parse_mass(input: &str) -> Vec<f64> {
+	input.lines().map(|line| line.trim().parse::<f64>().expect("error parsing mass number")).collect()
+}
+
+fn part_one(mass: &[f64]) -> f64 {
+	mass.iter().cloned().map(|mass| (mass / 3.0).floor() - 2.0).sum()
+}
+
+fn part_two(mass: &[f64]) -> f64 {
+	mass.iter().cloned().map(calculate_recursive_fuel).sum()
+}
+
+pub struct Day1;
+impl Solution for Day1 {
+	const DAY: u32 = 1;
+	fn part_one(input: &str) -> String {
+		part_one(&parse_mass(input)).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(part_two(&parse_mass(input)).to_string())
+	}
+}
@@ -0,0 +1,173 @@
+use std::io;
+use std::io::prelude::*;
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+pub fn main() {
+	let mut input = String::new();
+	let _ = io::stdin().read_to_string(&mut input).expect("error reading input");
+
+	let grid = Grid::parse(input.as_bytes());
+
+	let (mx, my, mc) = part_one(&grid);
+
+	println!("Part One\n========\n\nFrom `{}, {}` can be seen `{}` asteroids.\n", mx, my, mc);
+}
+
+pub struct Day10;
+impl Solution for Day10 {
+	const DAY: u32 = 10;
+	fn part_one(input: &str) -> String {
+		part_one(&Grid::parse(input.as_bytes())).2.to_string()
+	}
+}
+
+/// Calculates if the endpoint is visible from the start point.
+fn visible(grid: &Grid<&[u8]>, (startx, starty): (i32, i32), (endx, endy): (i32, i32)) -> bool {
+	let (stepx, stepy, steps) = step((endx - startx, endy - starty));
+	let mut x = startx;
+	let mut y = starty;
+	for _ in 0..steps - 1 {
+		x += stepx;
+		y += stepy;
+		if grid.get((x, y)) == Some(b'#') {
+			return false;
+		}
+	}
+	true
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+	// assert!(a >= 0 && b >= 0);
+	if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Computes the rational step size to reach `(dx, dy)`.
+fn step((dx, dy): (i32, i32)) -> (i32, i32, i32) {
+	let c = gcd(dx.abs(), dy.abs());
+	(dx / c, dy / c, c)
+}
+
+fn count_one(grid: &Grid<&[u8]>, (mx, my): (i32, i32)) -> i32 {
+	let mut count = 0;
+	for ay in 0..grid.height {
+		for ax in 0..grid.width {
+			if (ax, ay) != (mx, my) && grid.get((ax, ay)) == Some(b'#') && visible(grid, (mx, my), (ax, ay)) {
+				count += 1;
+			}
+		}
+	}
+	count
+}
+
+fn part_one(grid: &Grid<&[u8]>) -> (i32, i32, i32) {
+	let mut highest = 0;
+	let mut x = 0;
+	let mut y = 0;
+	for my in 0..grid.height {
+		for mx in 0..grid.width {
+			if grid.get((mx, my)) == Some(b'#') {
+				let count = count_one(grid, (mx, my));
+				if count > highest {
+					highest = count;
+					x = mx;
+					y = my;
+				}
+			}
+		}
+	}
+	(x, y, highest)
+}
+
+#[test]
+fn test1fd() {
+	let data = &b"\
+..#..
+.###.
+..#..
+"[..];
+	let grid = Grid::parse(data);
+	assert_eq!((2, 1, 4), part_one(&grid));
+}
+
+#[test]
+fn test1a() {
+	let data = &b"\
+......#.#.
+#..#.#....
+..#######.
+.#.#.###..
+.#..#.....
+..#....#.#
+#..#....#.
+.##.#..###
+##...#..#.
+.#....####
+"[..];
+	let grid = Grid::parse(data);
+	assert_eq!((5, 8, 33), part_one(&grid));
+}
+
+#[test]
+fn test2a() {
+	let data = &b"\
+#.#...#.#.
+.###....#.
+.#....#...
+##.#.#.#.#
+....#.#.#.
+.##..###.#
+..#...##..
+..##....##
+......#...
+.####.###.
+"[..];
+	let grid = Grid::parse(data);
+	assert_eq!((1, 2, 35), part_one(&grid));
+}
+
+#[test]
+fn test3a() {
+	let data = &b"\
+.#..#..###
+####.###.#
+....###.#.
+..###.##.#
+##.##.#.#.
+....###..#
+..#.#..#.#
+#..#.#.###
+.##...##.#
+.....#.#..
+"[..];
+	let grid = Grid::parse(data);
+	assert_eq!((6, 3, 41), part_one(&grid));
+}
+
+#[test]
+fn test4a() {
+	let data = &b"\
+.#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##
+"[..];
+	let grid = Grid::parse(data);
+	assert_eq!((11, 13, 210), part_one(&grid));
+}
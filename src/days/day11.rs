@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use crate::intcode::{load, Intcode, State};
+use crate::solution::Solution;
+
+pub fn main() {
+	let program = load::stdin::<i64>();
+
+	let result1 = paint(&program, false).len();
+	print!("Part One\n========\n\nThe robot paints `{}` panels at least once.\n\n", result1);
+
+	let result2 = render(&paint(&program, true));
+	print!("Part Two\n========\n\nThe registration identifier is:\n\n```\n{}```\n\n", result2);
+}
+
+pub struct Day11;
+impl Solution for Day11 {
+	const DAY: u32 = 11;
+	fn part_one(input: &str) -> String {
+		paint(&load::text::<i64>(input), false).len().to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(render(&paint(&load::text::<i64>(input), true)))
+	}
+}
+
+/// Runs the robot, returns the colour of every panel painted, `true` for white.
+fn paint(program: &[i64], start_white: bool) -> HashMap<(i32, i32), bool> {
+	let mut vm = Intcode::new(program);
+	let mut hull = HashMap::new();
+	if start_white {
+		hull.insert((0, 0), true);
+	}
+	let mut pos = (0, 0);
+	let mut dir = (0, -1);
+	let mut outputs = Vec::new();
+	loop {
+		match vm.run().expect("robot brain crashed") {
+			State::Running => (),
+			State::Input => vm.input.push_back(hull.get(&pos).cloned().unwrap_or(false) as i64),
+			State::Output(value) => {
+				outputs.push(value);
+				if let [colour, turn] = outputs[..] {
+					hull.insert(pos, colour == 1);
+					dir = if turn == 0 { (dir.1, -dir.0) } else { (-dir.1, dir.0) };
+					pos = (pos.0 + dir.0, pos.1 + dir.1);
+					outputs.clear();
+				}
+			},
+			State::Halted => break,
+		}
+	}
+	hull
+}
+
+fn render(hull: &HashMap<(i32, i32), bool>) -> String {
+	let white = hull.iter().filter(|&(_, &white)| white).map(|(&pos, _)| pos).collect::<Vec<_>>();
+	let mut image = String::new();
+	if white.is_empty() {
+		return image;
+	}
+	let (min_x, max_x) = (white.iter().map(|p| p.0).min().unwrap(), white.iter().map(|p| p.0).max().unwrap());
+	let (min_y, max_y) = (white.iter().map(|p| p.1).min().unwrap(), white.iter().map(|p| p.1).max().unwrap());
+	for y in min_y..=max_y {
+		for x in min_x..=max_x {
+			image.push(if hull.get(&(x, y)) == Some(&true) { '#' } else { ' ' });
+		}
+		image.push('\n');
+	}
+	image
+}
+
+#[cfg(test)]
+fn brain(outputs: &[i64]) -> Vec<i64> {
+	let mut program = outputs.iter().flat_map(|&value| vec![104, value]).collect::<Vec<_>>();
+	program.push(99);
+	program
+}
+
+#[test]
+fn test1() {
+	let program = brain(&[1,0,0,0,1,0,1,0,0,1,1,0,1,0]);
+	let hull = paint(&program, false);
+	assert_eq!(hull.len(), 6);
+	assert_eq!(render(&hull), "  #\n  #\n## \n");
+}
+
+#[test]
+fn test2() {
+	// Paints every panel in the colour it reads and turns right
+	let hull = paint(&[3,100,4,100,104,1,3,100,4,100,104,1,99], true);
+	assert_eq!(hull.len(), 2);
+	assert_eq!(render(&hull), "#\n");
+}
@@ -0,0 +1,144 @@
+/*!
+Usage: `cargo run --bin day13 [--watch] < input/day13.txt`.
+
+With `--watch` the screen is rendered in the terminal every time the autopilot moves the joystick.
+*/
+
+use std::env;
+use std::collections::HashMap;
+use crate::intcode::{load, Intcode, State};
+use crate::solution::Solution;
+
+const EMPTY: i64 = 0;
+const WALL: i64 = 1;
+const BLOCK: i64 = 2;
+const PADDLE: i64 = 3;
+const BALL: i64 = 4;
+
+pub fn main() {
+	let program = load::stdin::<i64>();
+	let watch = env::args().any(|arg| arg == "--watch");
+
+	let screen = play(&program, false, false);
+	let result1 = screen.count(BLOCK);
+	print!("Part One\n========\n\nThe screen shows `{}` block tiles.\n\n```\n{}```\n\n", result1, screen.render());
+
+	let screen = play(&program, true, watch);
+	let result2 = screen.score;
+	print!("Part Two\n========\n\nThe score after breaking the last block is `{}`.\n\n", result2);
+}
+
+pub struct Day13;
+impl Solution for Day13 {
+	const DAY: u32 = 13;
+	fn part_one(input: &str) -> String {
+		play(&load::text::<i64>(input), false, false).count(BLOCK).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(play(&load::text::<i64>(input), true, false).score.to_string())
+	}
+}
+
+#[derive(Clone, Debug, Default)]
+struct Screen {
+	tiles: HashMap<(i64, i64), i64>,
+	score: i64,
+	ball: i64,
+	paddle: i64,
+}
+impl Screen {
+	/// Draws an output triple, `(-1, 0)` is the score segment.
+	fn draw(&mut self, x: i64, y: i64, tile: i64) {
+		if (x, y) == (-1, 0) {
+			self.score = tile;
+			return;
+		}
+		match tile {
+			BALL => self.ball = x,
+			PADDLE => self.paddle = x,
+			_ => (),
+		}
+		self.tiles.insert((x, y), tile);
+	}
+	fn count(&self, tile: i64) -> usize {
+		self.tiles.values().filter(|&&t| t == tile).count()
+	}
+	fn render(&self) -> String {
+		let width = self.tiles.keys().map(|&(x, _)| x + 1).max().unwrap_or(0);
+		let height = self.tiles.keys().map(|&(_, y)| y + 1).max().unwrap_or(0);
+		let mut image = format!("Score: {}\n", self.score);
+		for y in 0..height {
+			for x in 0..width {
+				image.push(match self.tiles.get(&(x, y)).cloned().unwrap_or(EMPTY) {
+					WALL => '#',
+					BLOCK => '=',
+					PADDLE => '-',
+					BALL => 'o',
+					_ => ' ',
+				});
+			}
+			image.push('\n');
+		}
+		image
+	}
+}
+
+/// Runs the game, in free play the autopilot keeps the paddle under the ball.
+fn play(program: &[i64], free: bool, watch: bool) -> Screen {
+	let mut vm = Intcode::new(program);
+	if free {
+		vm.mem[0] = 2;
+	}
+	let mut screen = Screen::default();
+	let mut outputs = Vec::new();
+	loop {
+		match vm.run().expect("arcade crashed") {
+			State::Running => (),
+			State::Input => {
+				if watch {
+					print!("\x1b[H\x1b[2J{}", screen.render());
+				}
+				vm.input.push_back((screen.ball - screen.paddle).signum());
+			},
+			State::Output(value) => {
+				outputs.push(value);
+				if let [x, y, tile] = outputs[..] {
+					screen.draw(x, y, tile);
+					outputs.clear();
+				}
+			},
+			State::Halted => break,
+		}
+	}
+	screen
+}
+
+#[test]
+fn test1() {
+	let screen = play(&[104,1,104,2,104,3,104,6,104,5,104,4,99], false, false);
+	assert_eq!(screen.count(PADDLE), 1);
+	assert_eq!(screen.count(BALL), 1);
+	assert_eq!(screen.render(), "Score: 0\n       \n       \n -     \n       \n       \n      o\n");
+}
+
+#[test]
+fn test2() {
+	use crate::intcode::compiler;
+
+	// Scores the joystick position after drawing the paddle and the ball
+	let source = "
+		fn draw(x, y, tile) { output(x); output(y); output(tile); }
+		fn main() {
+			draw(0, 1, 2); draw(1, 1, 2);
+			draw(PADDLE, 5, 3);
+			draw(BALL, 4, 4);
+			let joystick = input();
+			draw(1, 1, 0);
+			draw(-1, 0, joystick + 10);
+		}";
+	let right = compiler::compile(&source.replace("PADDLE", "2").replace("BALL", "4")).unwrap();
+	let screen = play(&right, false, false);
+	assert_eq!((screen.score, screen.count(BLOCK)), (11, 1));
+	let left = compiler::compile(&source.replace("PADDLE", "2").replace("BALL", "0")).unwrap();
+	assert_eq!(play(&left, false, false).score, 9);
+}
@@ -0,0 +1,174 @@
+use std::collections::{HashMap, VecDeque};
+use crate::intcode::{load, Intcode, State};
+use crate::solution::Solution;
+
+pub fn main() {
+	let program = load::stdin::<i64>();
+	let map = explore(&program);
+	let oxygen = oxygen(&map);
+
+	let result1 = distances(&map, (0, 0))[&oxygen];
+	print!("Part One\n========\n\nThe oxygen system is `{}` movement commands away.\n\n```\n{}```\n\n", result1, render(&map));
+
+	let result2 = distances(&map, oxygen).values().cloned().max().unwrap();
+	print!("Part Two\n========\n\nIt takes `{}` minutes to fill the area with oxygen.\n\n", result2);
+}
+
+pub struct Day15;
+impl Solution for Day15 {
+	const DAY: u32 = 15;
+	fn part_one(input: &str) -> String {
+		let map = explore(&load::text::<i64>(input));
+		distances(&map, (0, 0))[&oxygen(&map)].to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		let map = explore(&load::text::<i64>(input));
+		distances(&map, oxygen(&map)).values().max().map(|minutes| minutes.to_string())
+	}
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Tile {
+	Wall,
+	Open,
+	Oxygen,
+}
+
+type Map = HashMap<(i32, i32), Tile>;
+
+/// Movement commands north, south, west and east.
+const MOVES: [(i64, (i32, i32)); 4] = [(1, (0, -1)), (2, (0, 1)), (3, (-1, 0)), (4, (1, 0))];
+
+/// Maps the area breadth first, keeping a snapshot of the droid's machine at every open position instead of walking back.
+fn explore(program: &[i64]) -> Map {
+	let mut map = Map::new();
+	map.insert((0, 0), Tile::Open);
+	let mut queue = VecDeque::new();
+	queue.push_back(((0, 0), Intcode::new(program)));
+	while let Some(((x, y), droid)) = queue.pop_front() {
+		for &(command, (dx, dy)) in &MOVES {
+			let pos = (x + dx, y + dy);
+			if map.contains_key(&pos) {
+				continue;
+			}
+			let mut droid = droid.clone();
+			droid.input.push_back(command);
+			let status = loop {
+				match droid.run().expect("droid crashed") {
+					State::Running => (),
+					State::Output(status) => break status,
+					state => panic!("droid stopped: {:?}", state),
+				}
+			};
+			let tile = match status {
+				0 => Tile::Wall,
+				1 => Tile::Open,
+				2 => Tile::Oxygen,
+				_ => panic!("invalid status: {}", status),
+			};
+			map.insert(pos, tile);
+			if tile != Tile::Wall {
+				queue.push_back((pos, droid));
+			}
+		}
+	}
+	map
+}
+
+fn oxygen(map: &Map) -> (i32, i32) {
+	map.iter().find(|&(_, &tile)| tile == Tile::Oxygen).map(|(&pos, _)| pos).expect("oxygen system not found")
+}
+
+/// Shortest distances from the position to every reachable open position.
+fn distances(map: &Map, from: (i32, i32)) -> HashMap<(i32, i32), usize> {
+	let mut dist = HashMap::new();
+	dist.insert(from, 0);
+	let mut queue = VecDeque::new();
+	queue.push_back(from);
+	while let Some((x, y)) = queue.pop_front() {
+		let d = dist[&(x, y)];
+		for &(_, (dx, dy)) in &MOVES {
+			let pos = (x + dx, y + dy);
+			if map.get(&pos).map(|&tile| tile != Tile::Wall).unwrap_or(false) && !dist.contains_key(&pos) {
+				dist.insert(pos, d + 1);
+				queue.push_back(pos);
+			}
+		}
+	}
+	dist
+}
+
+fn render(map: &Map) -> String {
+	let (min_x, max_x) = (map.keys().map(|p| p.0).min().unwrap(), map.keys().map(|p| p.0).max().unwrap());
+	let (min_y, max_y) = (map.keys().map(|p| p.1).min().unwrap(), map.keys().map(|p| p.1).max().unwrap());
+	let mut image = String::new();
+	for y in min_y..=max_y {
+		for x in min_x..=max_x {
+			image.push(match map.get(&(x, y)) {
+				_ if (x, y) == (0, 0) => 'D',
+				Some(Tile::Wall) => '#',
+				Some(Tile::Open) => '.',
+				Some(Tile::Oxygen) => 'O',
+				None => ' ',
+			});
+		}
+		image.push('\n');
+	}
+	image
+}
+
+#[cfg(test)]
+fn parse(s: &str) -> Map {
+	let mut map = Map::new();
+	for (y, line) in s.lines().enumerate() {
+		for (x, c) in line.chars().enumerate() {
+			let tile = match c {
+				'#' => Tile::Wall,
+				'.' => Tile::Open,
+				'O' => Tile::Oxygen,
+				_ => continue,
+			};
+			map.insert((x as i32, y as i32), tile);
+		}
+	}
+	map
+}
+
+#[test]
+fn test1() {
+	use crate::intcode::compiler;
+
+	// The droid in the maze of the oxygen example, starting at its top left open position
+	let source = "
+		fn open(x, y) {
+			return y == 1 && (x == 1 || x == 2) || y == 2 && (x == 1 || x == 3 || x == 4) || y == 3 && (x == 1 || x == 2 || x == 3);
+		}
+		fn main() {
+			let x = 1;
+			let y = 1;
+			while 1 {
+				let command = input();
+				let nx = x;
+				let ny = y;
+				if command == 1 { ny = y - 1; } else if command == 2 { ny = y + 1; } else if command == 3 { nx = x - 1; } else { nx = x + 1; }
+				if open(nx, ny) {
+					x = nx;
+					y = ny;
+					output(1 + (x == 2 && y == 3));
+				}
+				else {
+					output(0);
+				}
+			}
+		}";
+	let map = explore(&compiler::compile(source).unwrap());
+	assert_eq!(render(&map), " ##   \n#D.## \n#.#..#\n#.O.# \n ###  \n");
+	assert_eq!(oxygen(&map), (1, 2));
+	assert_eq!(distances(&map, (0, 0))[&(1, 2)], 3);
+}
+
+#[test]
+fn test2() {
+	let map = parse(" ##   \n#..## \n#.#..#\n#.O.# \n ###  ");
+	assert_eq!(distances(&map, oxygen(&map)).values().cloned().max(), Some(4));
+}
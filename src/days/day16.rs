@@ -0,0 +1,104 @@
+use std::{io, thread};
+use std::io::prelude::*;
+use crate::solution::Solution;
+
+fn parse(s: &str) -> Vec<u8> {
+	s.trim().bytes().map(|c| c - b'0').collect()
+}
+
+fn digits(signal: &[u8]) -> String {
+	signal.iter().map(|&d| (d + b'0') as char).collect()
+}
+
+pub fn main() {
+	let mut input = String::new();
+	let _ = io::stdin().read_to_string(&mut input).expect("error reading input");
+	let signal = parse(&input);
+
+	let result1 = digits(&fft(&signal, 100)[..8]);
+	print!("Part One\n========\n\nThe first eight digits after 100 phases are `{}`.\n\n", result1);
+
+	let result2 = digits(&real_signal(&signal, 100));
+	print!("Part Two\n========\n\nThe eight-digit message is `{}`.\n\n", result2);
+}
+
+pub struct Day16;
+impl Solution for Day16 {
+	const DAY: u32 = 16;
+	fn part_one(input: &str) -> String {
+		digits(&fft(&parse(input), 100)[..8])
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(digits(&real_signal(&parse(input), 100)))
+	}
+}
+
+/// Multiplier of the `j`th input digit for the `i`th output digit.
+fn pattern(i: usize, j: usize) -> i32 {
+	[0, 1, 0, -1][(j + 1) / (i + 1) % 4]
+}
+
+/// Computes the output digits starting at `start` into `out`.
+fn phase_range(signal: &[u8], start: usize, out: &mut [u8]) {
+	for (k, digit) in out.iter_mut().enumerate() {
+		let i = start + k;
+		// The pattern is zero for the first `i` digits
+		let sum = (i..signal.len()).map(|j| signal[j] as i32 * pattern(i, j)).sum::<i32>();
+		*digit = (sum.abs() % 10) as u8;
+	}
+}
+
+/// Runs the phases naively, every phase is split over the available threads.
+fn fft(signal: &[u8], phases: usize) -> Vec<u8> {
+	let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+	let chunk = signal.len().div_ceil(threads).max(1);
+	let mut signal = signal.to_vec();
+	let mut next = vec![0; signal.len()];
+	for _ in 0..phases {
+		thread::scope(|scope| {
+			for (index, out) in next.chunks_mut(chunk).enumerate() {
+				let signal = &signal;
+				scope.spawn(move || phase_range(signal, index * chunk, out));
+			}
+		});
+		std::mem::swap(&mut signal, &mut next);
+	}
+	signal
+}
+
+/// Decodes the message in the signal repeated 10000 times.
+///
+/// The message offset is in the second half of the signal, where the pattern is zero before and one from the output digit onwards.
+/// Every output digit is then the sum of the input digits from its position to the end, so only that suffix is computed.
+fn real_signal(signal: &[u8], phases: usize) -> Vec<u8> {
+	let offset = signal[..7].iter().fold(0, |acc, &d| acc * 10 + d as usize);
+	let len = signal.len() * 10000;
+	assert!(offset >= len / 2 && offset + 8 <= len, "message offset not in the second half of the signal");
+	let mut suffix = (offset..len).map(|i| signal[i % signal.len()]).collect::<Vec<u8>>();
+	for _ in 0..phases {
+		let mut sum = 0;
+		for digit in suffix.iter_mut().rev() {
+			sum = (sum + *digit) % 10;
+			*digit = sum;
+		}
+	}
+	suffix.truncate(8);
+	suffix
+}
+
+#[test]
+fn test1() {
+	let signal = parse("12345678");
+	assert_eq!(digits(&fft(&signal, 1)), "48226158");
+	assert_eq!(digits(&fft(&signal, 4)), "01029498");
+	assert_eq!(digits(&fft(&parse("80871224585914546619083218645595"), 100)[..8]), "24176176");
+	assert_eq!(digits(&fft(&parse("19617804207202209144916044189917"), 100)[..8]), "73745418");
+	assert_eq!(digits(&fft(&parse("69317163492948606335995924319873"), 100)[..8]), "52432133");
+}
+
+#[test]
+fn test2() {
+	assert_eq!(digits(&real_signal(&parse("03036732577212944063491565474664"), 100)), "84462026");
+	assert_eq!(digits(&real_signal(&parse("02935109699940807407585447034323"), 100)), "78725270");
+	assert_eq!(digits(&real_signal(&parse("03081770884921959731165446850517"), 100)), "53553731");
+}
@@ -0,0 +1,220 @@
+use crate::intcode::{load, Intcode, State};
+use crate::solution::Solution;
+
+pub fn main() {
+	let program = load::stdin::<i64>();
+
+	let view = camera(&program);
+	let grid = parse(&view);
+	let result1 = alignment(&grid);
+	print!("Part One\n========\n\nThe sum of the alignment parameters is `{}`.\n\n```\n{}```\n\n", result1, view);
+
+	let moves = path(&grid);
+	let (calls, functions) = compress(&moves, 3, 10, |routine| join(routine).len() <= 20).expect("no movement routines found");
+	let result2 = collect_dust(&program, &calls, &functions);
+	print!("Part Two\n========\n\nThe robot collects `{}` dust with the main routine `{}`.\n\n", result2, main_routine(&calls));
+}
+
+pub struct Day17;
+impl Solution for Day17 {
+	const DAY: u32 = 17;
+	fn part_one(input: &str) -> String {
+		alignment(&parse(&camera(&load::text::<i64>(input)))).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		let program = load::text::<i64>(input);
+		let moves = path(&parse(&camera(&program)));
+		let (calls, functions) = compress(&moves, 3, 10, |routine| join(routine).len() <= 20)?;
+		Some(collect_dust(&program, &calls, &functions).to_string())
+	}
+}
+
+/// Runs the program until it halts and returns its output.
+fn run(vm: &mut Intcode) -> Vec<i64> {
+	let mut output = Vec::new();
+	loop {
+		match vm.run().expect("program crashed") {
+			State::Running => (),
+			State::Output(value) => output.push(value),
+			State::Input => panic!("unexpected input request"),
+			State::Halted => return output,
+		}
+	}
+}
+
+fn camera(program: &[i64]) -> String {
+	let text = run(&mut Intcode::new(program)).into_iter().map(|value| value as u8 as char).collect::<String>();
+	text.trim_end().to_string() + "\n"
+}
+
+fn parse(view: &str) -> Vec<Vec<u8>> {
+	view.lines().filter(|line| !line.is_empty()).map(|line| line.bytes().collect()).collect()
+}
+
+fn at(grid: &[Vec<u8>], (x, y): (i32, i32)) -> u8 {
+	if x < 0 || y < 0 {
+		return b'.';
+	}
+	grid.get(y as usize).and_then(|row| row.get(x as usize)).cloned().unwrap_or(b'.')
+}
+
+fn is_scaffold(grid: &[Vec<u8>], pos: (i32, i32)) -> bool {
+	matches!(at(grid, pos), b'#' | b'^' | b'v' | b'<' | b'>')
+}
+
+fn alignment(grid: &[Vec<u8>]) -> i32 {
+	let mut sum = 0;
+	for y in 0..grid.len() as i32 {
+		for x in 0..grid[y as usize].len() as i32 {
+			if [(0, 0), (0, -1), (0, 1), (-1, 0), (1, 0)].iter().all(|&(dx, dy)| is_scaffold(grid, (x + dx, y + dy))) {
+				sum += x * y;
+			}
+		}
+	}
+	sum
+}
+
+/// Follows the scaffold from the robot, turning only at corners, as `R,8` style commands.
+///
+/// When the scaffold continues ahead of the robot the path starts with moving forward, eg. `4`.
+fn path(grid: &[Vec<u8>]) -> Vec<String> {
+	let (mut pos, mut dir) = (0..grid.len()).flat_map(|y| (0..grid[y].len()).map(move |x| (x as i32, y as i32)))
+		.find_map(|pos| match at(grid, pos) {
+			b'^' => Some((pos, (0, -1))),
+			b'v' => Some((pos, (0, 1))),
+			b'<' => Some((pos, (-1, 0))),
+			b'>' => Some((pos, (1, 0))),
+			_ => None,
+		})
+		.expect("robot not found");
+	let mut moves = Vec::new();
+	let steps = forward(grid, &mut pos, dir);
+	if steps > 0 {
+		moves.push(steps.to_string());
+	}
+	loop {
+		let (dx, dy): (i32, i32) = dir;
+		let (left, right) = ((dy, -dx), (-dy, dx));
+		let turn = if is_scaffold(grid, (pos.0 + left.0, pos.1 + left.1)) {
+			dir = left;
+			"L"
+		}
+		else if is_scaffold(grid, (pos.0 + right.0, pos.1 + right.1)) {
+			dir = right;
+			"R"
+		}
+		else {
+			return moves;
+		};
+		let steps = forward(grid, &mut pos, dir);
+		moves.push(format!("{},{}", turn, steps));
+	}
+}
+
+/// Moves along the scaffold in the direction until its end, returns the number of steps.
+fn forward(grid: &[Vec<u8>], pos: &mut (i32, i32), dir: (i32, i32)) -> usize {
+	let mut steps = 0;
+	while is_scaffold(grid, (pos.0 + dir.0, pos.1 + dir.1)) {
+		*pos = (pos.0 + dir.0, pos.1 + dir.1);
+		steps += 1;
+	}
+	steps
+}
+
+fn join<T: AsRef<str>>(items: &[T]) -> String {
+	items.iter().map(|item| item.as_ref()).collect::<Vec<_>>().join(",")
+}
+
+/// Splits the sequence into at most `max_calls` calls of at most `max_functions` functions which each must fit.
+///
+/// Returns the indices of the called functions and the functions.
+fn compress<T: Clone + Eq, F: Fn(&[T]) -> bool>(seq: &[T], max_functions: usize, max_calls: usize, fits: F) -> Option<(Vec<usize>, Vec<Vec<T>>)> {
+	struct Limits<'a, T> {
+		functions: usize,
+		calls: usize,
+		fits: &'a dyn Fn(&[T]) -> bool,
+	}
+	fn search<T: Clone + Eq>(seq: &[T], limits: &Limits<T>, calls: &mut Vec<usize>, functions: &mut Vec<Vec<T>>) -> bool {
+		if seq.is_empty() {
+			return true;
+		}
+		if calls.len() == limits.calls {
+			return false;
+		}
+		for index in 0..functions.len() {
+			if seq.starts_with(&functions[index]) {
+				calls.push(index);
+				if search(&seq[functions[index].len()..], limits, calls, functions) {
+					return true;
+				}
+				calls.pop();
+			}
+		}
+		if functions.len() < limits.functions {
+			for len in 1..=seq.len() {
+				if !(limits.fits)(&seq[..len]) {
+					break;
+				}
+				functions.push(seq[..len].to_vec());
+				calls.push(functions.len() - 1);
+				if search(&seq[len..], limits, calls, functions) {
+					return true;
+				}
+				calls.pop();
+				functions.pop();
+			}
+		}
+		false
+	}
+	let mut calls = Vec::new();
+	let mut functions = Vec::new();
+	let limits = Limits { functions: max_functions, calls: max_calls, fits: &fits };
+	if search(seq, &limits, &mut calls, &mut functions) { Some((calls, functions)) } else { None }
+}
+
+/// Returns the main routine in its ASCII form.
+fn main_routine(calls: &[usize]) -> String {
+	join(&calls.iter().map(|&index| ((b'A' + index as u8) as char).to_string()).collect::<Vec<_>>())
+}
+
+fn collect_dust(program: &[i64], calls: &[usize], functions: &[Vec<String>]) -> i64 {
+	let mut vm = Intcode::new(program);
+	vm.mem[0] = 2;
+	let mut input = main_routine(calls) + "\n";
+	for function in functions {
+		input += &join(function);
+		input += "\n";
+	}
+	input += "n\n";
+	vm.input.extend(input.bytes().map(i64::from));
+	// The dust is the last output, after the prompts and the final camera view
+	*run(&mut vm).last().expect("no dust reported")
+}
+
+#[test]
+fn test1() {
+	let grid = parse("..#..........\n..#..........\n#######...###\n#.#...#...#.#\n#############\n..#...#...#..\n..#####...^..\n");
+	assert_eq!(alignment(&grid), 76);
+}
+
+#[test]
+fn test2() {
+	let grid = parse("\
+		#######...#####\n#.....#...#...#\n#.....#...#...#\n......#...#...#\n......#...###.#\n\
+		......#.....#.#\n^########...#.#\n......#.#...#.#\n......#########\n........#...#..\n\
+		....#########..\n....#...#......\n....#...#......\n....#...#......\n....#####......\n");
+	let moves = path(&grid);
+	assert_eq!(join(&moves), "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2");
+
+	let (calls, functions) = compress(&moves, 3, 10, |routine| join(routine).len() <= 20).unwrap();
+	assert!(main_routine(&calls).len() <= 20 && functions.len() <= 3);
+	assert_eq!(calls.iter().flat_map(|&index| functions[index].clone()).collect::<Vec<_>>(), moves);
+	assert_eq!(compress(&moves, 2, 10, |routine| routine.len() <= 2), None);
+	assert_eq!(compress(&moves, 3, 3, |routine| routine.len() <= 4), None);
+}
+
+#[test]
+fn test_straight_start() {
+	let grid = parse("#####\n#....\n#....\n^....\n");
+	assert_eq!(join(&path(&grid)), "3,R,4");
+}
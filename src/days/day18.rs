@@ -0,0 +1,187 @@
+use std::io;
+use std::io::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+pub fn main() {
+	let mut input = String::new();
+	let _ = io::stdin().read_to_string(&mut input).expect("error reading input");
+	let grid = Grid::parse(input.as_bytes());
+
+	let result1 = collect_keys(&grid);
+	print!("Part One\n========\n\nThe shortest path collecting all keys is `{}` steps.\n\n", result1);
+
+	let result2 = collect_keys(&split(&grid));
+	print!("Part Two\n========\n\nThe four robots collect all keys in `{}` steps.\n\n", result2);
+}
+
+pub struct Day18;
+impl Solution for Day18 {
+	const DAY: u32 = 18;
+	fn part_one(input: &str) -> String {
+		collect_keys(&Grid::parse(input.as_bytes())).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(collect_keys(&split(&Grid::parse(input.as_bytes()))).to_string())
+	}
+}
+
+/// Replaces the entrance by four entrances separated by walls.
+fn split<T: AsRef<[u8]>>(grid: &Grid<T>) -> Grid<Vec<u8>> {
+	let (x, y) = grid.find(b'@').expect("entrance not found");
+	let mut grid = grid.to_owned();
+	for (dy, row) in [b"@#@", b"###", b"@#@"].iter().enumerate() {
+		for (dx, &cell) in row.iter().enumerate() {
+			grid.set((x + dx as i32 - 1, y + dy as i32 - 1), cell);
+		}
+	}
+	grid
+}
+
+/// A path to a key with the doors on the way as a bitmask.
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+	key: u8,
+	dist: u32,
+	doors: u32,
+}
+
+/// Finds the paths from the position to every reachable key.
+fn edges<T: AsRef<[u8]>>(grid: &Grid<T>, from: (i32, i32)) -> Vec<Edge> {
+	let mut seen = HashSet::new();
+	seen.insert(from);
+	let mut queue = VecDeque::new();
+	queue.push_back((from, 0, 0));
+	let mut edges = Vec::new();
+	while let Some(((x, y), dist, mut doors)) = queue.pop_front() {
+		match grid.get((x, y)) {
+			Some(cell @ b'a'..=b'z') if dist > 0 => edges.push(Edge { key: cell - b'a', dist, doors }),
+			Some(cell @ b'A'..=b'Z') => doors |= 1 << (cell - b'A'),
+			_ => (),
+		}
+		for &(dx, dy) in &[(0, -1), (0, 1), (-1, 0), (1, 0)] {
+			let pos = (x + dx, y + dy);
+			if grid.get(pos).map(|cell| cell != b'#').unwrap_or(false) && seen.insert(pos) {
+				queue.push_back((pos, dist + 1, doors));
+			}
+		}
+	}
+	edges
+}
+
+/// Dijkstra over the positions of the robots and the keys held.
+fn collect_keys<T: AsRef<[u8]>>(grid: &Grid<T>) -> u32 {
+	// Points of interest are the keys 0..26 followed by the entrances
+	let mut robots = Vec::new();
+	let mut graph = HashMap::new();
+	let mut all_keys = 0u32;
+	for (pos, cell) in grid.cells() {
+		let point = match cell {
+			b'a'..=b'z' => {
+				all_keys |= 1 << (cell - b'a');
+				cell - b'a'
+			},
+			b'@' => {
+				robots.push(26 + robots.len() as u8);
+				*robots.last().unwrap()
+			},
+			_ => continue,
+		};
+		graph.insert(point, edges(grid, pos));
+	}
+
+	let mut best = HashMap::new();
+	let mut heap = BinaryHeap::new();
+	heap.push(Reverse((0, robots, 0u32)));
+	while let Some(Reverse((dist, robots, keys))) = heap.pop() {
+		if keys == all_keys {
+			return dist;
+		}
+		if best.get(&(robots.clone(), keys)).map(|&d| d < dist).unwrap_or(false) {
+			continue;
+		}
+		for (index, point) in robots.iter().enumerate() {
+			for edge in &graph[point] {
+				if keys & (1 << edge.key) != 0 || edge.doors & !keys != 0 {
+					continue;
+				}
+				let mut next = robots.clone();
+				next[index] = edge.key;
+				let state = (next, keys | 1 << edge.key);
+				let dist = dist + edge.dist;
+				if best.get(&state).map(|&d| dist < d).unwrap_or(true) {
+					best.insert(state.clone(), dist);
+					heap.push(Reverse((dist, state.0, state.1)));
+				}
+			}
+		}
+	}
+	panic!("not all keys can be collected")
+}
+
+#[test]
+fn test1() {
+	let solve = |s: &str| collect_keys(&Grid::parse(s.as_bytes()));
+	assert_eq!(solve("#########\n#b.A.@.a#\n#########\n"), 8);
+	assert_eq!(solve("\
+########################
+#f.D.E.e.C.b.A.@.a.B.c.#
+######################.#
+#d.....................#
+########################
+"), 86);
+	assert_eq!(solve("\
+########################
+#...............b.C.D.f#
+#.######################
+#.....@.a.B.c.d.A.e.F.g#
+########################
+"), 132);
+	assert_eq!(solve("\
+#################
+#i.G..c...e..H.p#
+########.########
+#j.A..b...f..D.o#
+########@########
+#k.E..a...g..B.n#
+########.########
+#l.F..d...h..C.m#
+#################
+"), 136);
+	assert_eq!(solve("\
+########################
+#@..............ac.GI.b#
+###d#e#f################
+###A#B#C################
+###g#h#i################
+########################
+"), 81);
+}
+
+#[test]
+fn test2() {
+	let solve = |s: &str| collect_keys(&split(&Grid::parse(s.as_bytes())));
+	assert_eq!(solve("#######\n#a.#Cd#\n##...##\n##.@.##\n##...##\n#cB#Ab#\n#######\n"), 8);
+	assert_eq!(solve("\
+#############
+#DcBa.#.GhKl#
+#.###...#I###
+#e#d#.@.#j#k#
+###C#...###J#
+#fEbA.#.FgHi#
+#############
+"), 32);
+	assert_eq!(solve("\
+#############
+#g#f.D#..h#l#
+#F###e#E###.#
+#dCba...BcIJ#
+#####.@.#####
+#nK.L...G...#
+#M###N#H###.#
+#o#m..#i#jk.#
+#############
+"), 72);
+}
@@ -0,0 +1,109 @@
+use std::cell::Cell;
+use crate::intcode::{load, Intcode, State};
+use crate::solution::Solution;
+
+pub fn main() {
+	let program = load::stdin::<i64>();
+	let drone = Drone::new(&program);
+
+	let result1 = count(&drone, 50);
+	print!("Part One\n========\n\nThe beam affects `{}` points in the 50x50 area, using `{}` Intcode runs.\n\n", result1, drone.runs.get());
+
+	drone.runs.set(0);
+	let (x, y) = square(&drone, 100);
+	let result2 = x * 10000 + y;
+	print!("Part Two\n========\n\nThe closest 100x100 square is at `{}, {}` giving `{}`, using `{}` Intcode runs.\n\n", x, y, result2, drone.runs.get());
+}
+
+pub struct Day19;
+impl Solution for Day19 {
+	const DAY: u32 = 19;
+	fn part_one(input: &str) -> String {
+		count(&Drone::new(&load::text::<i64>(input)), 50).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		let (x, y) = square(&Drone::new(&load::text::<i64>(input)), 100);
+		Some((x * 10000 + y).to_string())
+	}
+}
+
+/// Deploys drones from a snapshot of the program.
+struct Drone {
+	snapshot: Intcode,
+	runs: Cell<usize>,
+}
+impl Drone {
+	fn new(program: &[i64]) -> Drone {
+		Drone { snapshot: Intcode::new(program), runs: Cell::new(0) }
+	}
+	/// Returns if the point is affected by the tractor beam.
+	fn pulled(&self, x: i64, y: i64) -> bool {
+		self.runs.set(self.runs.get() + 1);
+		let mut vm = self.snapshot.clone();
+		vm.input.extend(&[x, y]);
+		loop {
+			match vm.run().expect("drone crashed") {
+				State::Running => (),
+				State::Output(value) => return value == 1,
+				state => panic!("drone stopped: {:?}", state),
+			}
+		}
+	}
+}
+
+fn count(drone: &Drone, size: i64) -> usize {
+	(0..size).flat_map(|y| (0..size).map(move |x| (x, y))).filter(|&(x, y)| drone.pulled(x, y)).count()
+}
+
+/// Finds the top left corner of the closest square fitting in the beam.
+///
+/// Follows the left edge of the beam row by row, the square fits when its top right corner on the row `size - 1` above is pulled.
+fn square(drone: &Drone, size: i64) -> (i64, i64) {
+	let mut left = 0;
+	for y in size - 1.. {
+		// Rows close to the emitter can be empty, give up on the row after scanning as far as the row is long
+		let mut x = left;
+		while x <= left + y && !drone.pulled(x, y) {
+			x += 1;
+		}
+		if x > left + y {
+			continue;
+		}
+		left = x;
+		if drone.pulled(x + size - 1, y - (size - 1)) {
+			return (x, y - (size - 1));
+		}
+	}
+	unreachable!()
+}
+
+#[cfg(test)]
+fn beam() -> Vec<i64> {
+	use crate::intcode::compiler;
+	// A beam between the slopes 0.7 and 1.3
+	compiler::compile("fn main() { let x = input(); let y = input(); output(x * 10 >= y * 7 && x * 10 <= y * 13); }").unwrap()
+}
+
+#[test]
+fn test1() {
+	let drone = Drone::new(&beam());
+	let expected = (0..10).map(|y: i64| (0..10).filter(|&x| x * 10 >= y * 7 && x * 10 <= y * 13).count()).sum::<usize>();
+	assert_eq!(count(&drone, 10), expected);
+	assert_eq!(drone.runs.get(), 100);
+}
+
+#[test]
+fn test2() {
+	let drone = Drone::new(&beam());
+	let pulled = |x: i64, y: i64| x * 10 >= y * 7 && x * 10 <= y * 13;
+	for &size in &[1, 2, 10] {
+		// Brute force the closest square by distance from the emitter
+		let expected = (0..200).flat_map(|y| (0..200).map(move |x| (x, y)))
+			.filter(|&(x, y)| pulled(x, y) && pulled(x + size - 1, y) && pulled(x, y + size - 1))
+			.min_by_key(|&(x, y)| (x + y, y))
+			.unwrap();
+		drone.runs.set(0);
+		assert_eq!(square(&drone, size), expected);
+		assert!(drone.runs.get() < 400);
+	}
+}
@@ -0,0 +1,89 @@
+use crate::intcode::load;
+use crate::intcode::backend::{self, Status};
+use crate::solution::Solution;
+#[cfg(test)]
+use crate::intcode::inspect::assert_memory;
+
+pub fn main() {
+	let mut program = load::stdin::<u32>();
+
+	program[1] = 12;
+	program[2] = 2;
+
+	let result1 = execute(&mut program.clone());
+
+	print!("Part One\n========\n\nResult: `{}`\n\n", result1);
+
+	print!("Part Two\n========\n\n");
+	let (noun, verb, result2) = find_inputs(&program);
+	print!("Noun: `{}`\nVerb: `{}`\nResult: `{}`\n", noun, verb, result2);
+}
+
+pub struct Day2;
+impl Solution for Day2 {
+	const DAY: u32 = 2;
+	fn part_one(input: &str) -> String {
+		let mut program = load::text::<u32>(input);
+		program[1] = 12;
+		program[2] = 2;
+		execute(&mut program).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		let (noun, verb, _) = find_inputs(&load::text::<u32>(input));
+		Some((100 * noun + verb).to_string())
+	}
+}
+
+//----------------------------------------------------------------
+// Part One
+
+fn execute(program: &mut [u32]) -> u32 {
+	match backend::day2_execute(program, u64::MAX) {
+		Ok(Status::Halted) => program[0],
+		Ok(status) => panic!("program stopped: {:?}", status),
+		Err(fault) => panic!("{}", fault),
+	}
+}
+
+#[test]
+fn test1() {
+	let mut program = [1,0,0,0,99];
+	assert_eq!(2, execute(&mut program));
+	assert_memory(&program, &[2,0,0,0,99]);
+}
+#[test]
+fn test2() {
+	let mut program = [2,3,0,3,99];
+	assert_eq!(2, execute(&mut program));
+	assert_memory(&program, &[2,3,0,6,99]);
+}
+#[test]
+fn test3() {
+	let mut program = [2,4,4,5,99,0];
+	assert_eq!(2, execute(&mut program));
+	assert_memory(&program, &[2,4,4,5,99,9801]);
+}
+#[test]
+fn test4() {
+	let mut program = [1,1,1,4,99,5,6,0,99];
+	assert_eq!(30, execute(&mut program));
+	assert_memory(&program, &[30,1,1,4,2,5,6,0,99]);
+}
+
+//----------------------------------------------------------------
+// Part Two
+
+fn find_inputs(program: &[u32]) -> (u32, u32, u32) {
+	for noun in 0..=99 {
+		for verb in 0..=99 {
+			let mut program = program.to_vec();
+			program[1] = noun;
+			program[2] = verb;
+			let result = execute(&mut program);
+			if result == 19690720 {
+				return (noun, verb, 100 * noun + verb);
+			}
+		}
+	}
+	panic!("No solutions found!");
+}
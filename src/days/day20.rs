@@ -0,0 +1,239 @@
+use std::io;
+use std::io::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+use itertools::Itertools;
+use crate::grid::Grid;
+use crate::solution::Solution;
+
+pub fn main() {
+	let mut input = String::new();
+	let _ = io::stdin().read_to_string(&mut input).expect("error reading input");
+	let maze = Maze::parse(&input);
+
+	let result1 = maze.solve(false).expect("no path through the maze");
+	print!("Part One\n========\n\nThe shortest path from AA to ZZ takes `{}` steps.\n\n", result1);
+
+	let result2 = maze.solve(true).expect("no path through the recursive maze");
+	print!("Part Two\n========\n\nThe shortest path through the recursive maze takes `{}` steps.\n\n", result2);
+}
+
+pub struct Day20;
+impl Solution for Day20 {
+	const DAY: u32 = 20;
+	fn part_one(input: &str) -> String {
+		Maze::parse(input).solve(false).expect("no path through the maze").to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Maze::parse(input).solve(true).map(|steps| steps.to_string())
+	}
+}
+
+/// The other end of a portal and the change in level when taking it.
+#[derive(Copy, Clone, Debug)]
+struct Portal {
+	target: (i32, i32),
+	depth: i32,
+}
+
+struct Maze {
+	grid: Grid<Vec<u8>>,
+	portals: HashMap<(i32, i32), Portal>,
+	start: (i32, i32),
+	end: (i32, i32),
+}
+
+const DIRS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+impl Maze {
+	fn parse(input: &str) -> Maze {
+		let grid = Grid::from_lines(input, b' ');
+		// Labels are read top to bottom or left to right next to the open tile they belong to
+		// A label is outer when its tile is on the edge of the bounding box of the maze's tiles
+		let tiles = grid.cells().filter(|&(_, cell)| cell == b'#' || cell == b'.').map(|(pos, _)| pos).collect::<Vec<_>>();
+		let (left, right) = tiles.iter().map(|&(x, _)| x).minmax().into_option().expect("empty maze");
+		let (top, bottom) = tiles.iter().map(|&(_, y)| y).minmax().into_option().expect("empty maze");
+		let mut labels = HashMap::<[u8; 2], Vec<((i32, i32), bool)>>::new();
+		for ((x, y), cell) in grid.cells() {
+			if cell != b'.' {
+				continue;
+			}
+			for &(dx, dy) in &DIRS {
+				let near = grid.get((x + dx, y + dy)).unwrap_or(b' ');
+				let far = grid.get((x + 2 * dx, y + 2 * dy)).unwrap_or(b' ');
+				if near.is_ascii_uppercase() && far.is_ascii_uppercase() {
+					let label = if dx + dy < 0 { [far, near] } else { [near, far] };
+					let outer = x == left || x == right || y == top || y == bottom;
+					labels.entry(label).or_default().push(((x, y), outer));
+				}
+			}
+		}
+
+		let mut portals = HashMap::new();
+		for (label, ends) in &labels {
+			match ends[..] {
+				[_] if label == b"AA" || label == b"ZZ" => (),
+				[(a, a_outer), (b, b_outer)] => {
+					let depth = |outer| if outer { -1 } else { 1 };
+					portals.insert(a, Portal { target: b, depth: depth(a_outer) });
+					portals.insert(b, Portal { target: a, depth: depth(b_outer) });
+				},
+				_ => panic!("invalid portal {}", String::from_utf8_lossy(label)),
+			}
+		}
+		let start = labels.get(b"AA").expect("entrance AA not found")[0].0;
+		let end = labels.get(b"ZZ").expect("exit ZZ not found")[0].0;
+		Maze { grid, portals, start, end }
+	}
+
+	/// Breadth first search over positions and levels, in the flat maze every portal stays on level zero.
+	fn solve(&self, recursive: bool) -> Option<usize> {
+		// On a shortest path every level is entered for the last time on the way down through an inner portal,
+		// arriving on an outer tile, and left for the first time on the way back up, arriving on an inner tile.
+		// Two levels with the same pair of tiles would allow cutting out the detour in between,
+		// so the path never goes deeper than the number of outer and inner tile pairs.
+		let ends = self.portals.len() as i32 / 2;
+		let max_level = ends * ends;
+		let mut seen = HashSet::new();
+		seen.insert((self.start, 0));
+		let mut queue = VecDeque::new();
+		queue.push_back((self.start, 0, 0));
+		while let Some(((x, y), level, dist)) = queue.pop_front() {
+			if (x, y) == self.end && level == 0 {
+				return Some(dist);
+			}
+			let mut next = DIRS.iter().map(|&(dx, dy)| ((x + dx, y + dy), level))
+				.filter(|&(pos, _)| self.grid.get(pos) == Some(b'.'))
+				.collect::<Vec<_>>();
+			if let Some(portal) = self.portals.get(&(x, y)) {
+				let level = if recursive { level + portal.depth } else { 0 };
+				if (0..=max_level).contains(&level) {
+					next.push((portal.target, level));
+				}
+			}
+			for state in next {
+				if seen.insert(state) {
+					queue.push_back((state.0, state.1, dist + 1));
+				}
+			}
+		}
+		None
+	}
+}
+
+#[cfg(test)]
+const EXAMPLE1: &str = "
+         A
+         A
+  #######.#########
+  #######.........#
+  #######.#######.#
+  #######.#######.#
+  #######.#######.#
+  #####  B    ###.#
+BC...##  C    ###.#
+  ##.##       ###.#
+  ##...DE  F  ###.#
+  #####    G  ###.#
+  #########.#####.#
+DE..#######...###.#
+  #.#########.###.#
+FG..#########.....#
+  ###########.#####
+             Z
+             Z
+";
+
+#[cfg(test)]
+const EXAMPLE2: &str = "
+                   A
+                   A
+  #################.#############
+  #.#...#...................#.#.#
+  #.#.#.###.###.###.#########.#.#
+  #.#.#.......#...#.....#.#.#...#
+  #.#########.###.#####.#.#.###.#
+  #.............#.#.....#.......#
+  ###.###########.###.#.#.#.#####
+  #.....#        A   C    #.#.#.#
+  #######        S   P    #####.#
+  #.#...#                 #......VT
+  #.#.#.#                 #.#####
+  #...#.#               YN....#.#
+  #.###.#                 #####.#
+DI....#.#                 #.....#
+  #####.#                 #.###.#
+ZZ......#               QG....#..AS
+  ###.###                 #######
+JO..#.#.#                 #.....#
+  #.#.#.#                 ###.#.#
+  #...#..DI             BU....#..LF
+  #####.#                 #.#####
+YN......#               VT..#....QG
+  #.###.#                 #.###.#
+  #.#...#                 #.....#
+  ###.###    J L     J    #.#.###
+  #.....#    O F     P    #.#...#
+  #.###.#####.#.#####.#####.###.#
+  #...#.#.#...#.....#.....#.#...#
+  #.#####.###.###.#.#.#########.#
+  #...#.#.....#...#.#.#.#.....#.#
+  #.###.#####.###.###.#.#.#######
+  #.#.........#...#.............#
+  #########.###.###.#############
+           B   J   C
+           U   P   P
+";
+
+#[cfg(test)]
+const EXAMPLE3: &str = "
+             Z L X W       C
+             Z P Q B       K
+  ###########.#.#.#.#######.###############
+  #...#.......#.#.......#.#.......#.#.#...#
+  ###.#.#.#.#.#.#.#.###.#.#.#######.#.#.###
+  #.#...#.#.#...#.#.#...#...#...#.#.......#
+  #.###.#######.###.###.#.###.###.#.#######
+  #...#.......#.#...#...#.............#...#
+  #.#########.#######.#.#######.#######.###
+  #...#.#    F       R I       Z    #.#.#.#
+  #.###.#    D       E C       H    #.#.#.#
+  #.#...#                           #...#.#
+  #.###.#                           #.###.#
+  #.#....OA                       WB..#.#..ZH
+  #.###.#                           #.#.#.#
+CJ......#                           #.....#
+  #######                           #######
+  #.#....CK                         #......IC
+  #.###.#                           #.###.#
+  #.....#                           #...#.#
+  ###.###                           #.#.#.#
+XF....#.#                         RF..#.#.#
+  #####.#                           #######
+  #......CJ                       NM..#...#
+  ###.#.#                           #.###.#
+RE....#.#                           #......RF
+  ###.###        X   X       L      #.#.#.#
+  #.....#        F   Q       P      #.#.#.#
+  ###.###########.###.#######.#########.###
+  #.....#...#.....#.......#...#.....#.#...#
+  #####.#.###.#######.#######.###.###.#.#.#
+  #.......#.......#.#.#.#.#...#...#...#.#.#
+  #####.###.#####.#.#.#.#.###.###.#.###.###
+  #.......#.....#.#...#...............#...#
+  #############.#.#.###.###################
+               A O F   N
+               A A D   M
+";
+
+#[test]
+fn test1() {
+	assert_eq!(Maze::parse(&EXAMPLE1[1..]).solve(false), Some(23));
+	assert_eq!(Maze::parse(&EXAMPLE2[1..]).solve(false), Some(58));
+}
+
+#[test]
+fn test2() {
+	assert_eq!(Maze::parse(&EXAMPLE1[1..]).solve(true), Some(26));
+	assert_eq!(Maze::parse(&EXAMPLE2[1..]).solve(true), None);
+	assert_eq!(Maze::parse(&EXAMPLE3[1..]).solve(true), Some(396));
+}
@@ -0,0 +1,227 @@
+use std::fmt;
+use crate::intcode::{load, Intcode, State};
+use crate::solution::Solution;
+
+pub fn main() {
+	let program = load::stdin::<i64>();
+
+	for &(part, run) in &[("Part One\n========", false), ("Part Two\n========", true)] {
+		match search(run, |script| springdroid(&program, script)) {
+			Ok((script, damage)) => print!("{}\n\nThe hull damage is `{}` with the script:\n\n```\n{}```\n\n", part, damage, script),
+			Err(failure) => print!("{}\n\nNo script found, the last attempt:\n\n```\n{}```\n\n", part, failure),
+		}
+	}
+}
+
+pub struct Day21;
+impl Solution for Day21 {
+	const DAY: u32 = 21;
+	fn part_one(input: &str) -> String {
+		let program = load::text::<i64>(input);
+		search(false, |script| springdroid(&program, script)).expect("no script found").1.to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		let program = load::text::<i64>(input);
+		search(true, |script| springdroid(&program, script)).ok().map(|(_, damage)| damage.to_string())
+	}
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Op {
+	And,
+	Or,
+	Not,
+}
+
+/// A SpringScript instruction, registers are their letters.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Instr {
+	op: Op,
+	src: u8,
+	dest: u8,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Script {
+	instrs: Vec<Instr>,
+	/// Senses nine tiles ahead with `RUN` instead of four with `WALK`.
+	run: bool,
+}
+impl Script {
+	/// Evaluates the script, `ground` tells if there's ground at the sensors `A` onwards.
+	fn jump(&self, ground: &[bool]) -> bool {
+		let (mut t, mut j) = (false, false);
+		for instr in &self.instrs {
+			let src = match instr.src {
+				b'T' => t,
+				b'J' => j,
+				sensor => ground[(sensor - b'A') as usize],
+			};
+			let dest = if instr.dest == b'T' { &mut t } else { &mut j };
+			*dest = match instr.op {
+				Op::And => src && *dest,
+				Op::Or => src || *dest,
+				Op::Not => !src,
+			};
+		}
+		j
+	}
+}
+impl fmt::Display for Script {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for instr in &self.instrs {
+			let op = match instr.op { Op::And => "AND", Op::Or => "OR", Op::Not => "NOT" };
+			writeln!(f, "{} {} {}", op, instr.src as char, instr.dest as char)?;
+		}
+		writeln!(f, "{}", if self.run { "RUN" } else { "WALK" })
+	}
+}
+
+/// Runs the script on the springdroid, returns the hull damage or the ASCII output up to the first frame of the droid's fall.
+///
+/// The rest of the animation isn't kept, the search only uses the hull in the first frame.
+fn springdroid(program: &[i64], script: &Script) -> Result<i64, String> {
+	let mut vm = Intcode::new(program);
+	vm.input.extend(script.to_string().bytes().map(i64::from));
+	let mut text = String::new();
+	let mut framed = false;
+	loop {
+		match vm.run().expect("springdroid crashed") {
+			State::Running => (),
+			State::Output(value) if (0..128).contains(&value) => {
+				if !framed {
+					text.push(value as u8 as char);
+					framed = value == 10 && failed_hull(&text).is_some();
+				}
+			},
+			State::Output(value) => return Ok(value),
+			State::Input => panic!("springdroid wants more input"),
+			State::Halted => return Err(text),
+		}
+	}
+}
+
+/// Extracts the hull from the first frame of the fall animation, the droid starts on its first tile.
+fn failed_hull(failure: &str) -> Option<Vec<bool>> {
+	let mut lines = failure.lines().skip_while(|line| !line.starts_with('@'));
+	lines.nth(1).map(|line| line.bytes().map(|c| c == b'#').collect())
+}
+
+/// Simulates the droid walking from the start of the hull, beyond its end is ground.
+fn survives(script: &Script, hull: &[bool]) -> bool {
+	let ground = |pos: usize| hull.get(pos).cloned().unwrap_or(true);
+	let mut pos = 0;
+	while pos < hull.len() {
+		if !ground(pos) {
+			return false;
+		}
+		let sensors = (1..=9).map(|offset| ground(pos + offset)).collect::<Vec<_>>();
+		pos += if script.jump(&sensors) { 4 } else { 1 };
+	}
+	true
+}
+
+/// Scripts jumping when there's a hole among the next tiles and ground to land on, with optional lookahead after landing.
+///
+/// `J = (!A | !B | !C for a subset) & D & lookahead`, shortest first.
+fn candidates(run: bool) -> Vec<Script> {
+	let instr = |op, src, dest| Instr { op, src, dest };
+	let lookaheads: Vec<Vec<Instr>> = if run {
+		vec![
+			vec![],
+			vec![instr(Op::And, b'E', b'J')],
+			vec![instr(Op::And, b'H', b'J')],
+			vec![instr(Op::Not, b'E', b'T'), instr(Op::Not, b'T', b'T'), instr(Op::Or, b'H', b'T'), instr(Op::And, b'T', b'J')],
+		]
+	}
+	else {
+		vec![vec![]]
+	};
+	let mut scripts = Vec::new();
+	for lookahead in &lookaheads {
+		for subset in 1..8 {
+			let holes = (0..3).filter(|bit| subset & (1 << bit) != 0).map(|bit| b'A' + bit as u8).collect::<Vec<_>>();
+			let mut instrs = vec![instr(Op::Not, holes[0], b'J')];
+			for &hole in &holes[1..] {
+				instrs.push(instr(Op::Not, hole, b'T'));
+				instrs.push(instr(Op::Or, b'T', b'J'));
+			}
+			instrs.push(instr(Op::And, b'D', b'J'));
+			instrs.extend(lookahead);
+			scripts.push(Script { instrs, run });
+		}
+	}
+	scripts.sort_by_key(|script| script.instrs.len());
+	scripts
+}
+
+/// Tries candidate scripts, skipping those which fall into a hull seen in an earlier failure.
+///
+/// Returns the working script and the hull damage or the last failure.
+fn search<F: FnMut(&Script) -> Result<i64, String>>(run: bool, mut try_script: F) -> Result<(Script, i64), String> {
+	let mut hulls: Vec<Vec<bool>> = Vec::new();
+	let mut failure = String::new();
+	for script in candidates(run) {
+		if !hulls.iter().all(|hull| survives(&script, hull)) {
+			continue;
+		}
+		match try_script(&script) {
+			Ok(damage) => return Ok((script, damage)),
+			Err(text) => {
+				hulls.extend(failed_hull(&text));
+				failure = text;
+			},
+		}
+	}
+	Err(failure)
+}
+
+#[cfg(test)]
+fn hull(s: &str) -> Vec<bool> {
+	s.bytes().map(|c| c == b'#').collect()
+}
+
+#[test]
+fn test1() {
+	let failure = "Input instructions:\n\nWalking...\n\n\nDidn't make it across:\n\n.................\n.................\n@................\n#####.#..########\n\n";
+	assert_eq!(failed_hull(failure), Some(hull("#####.#..########")));
+
+	let walk = candidates(false);
+	let script = walk.iter().find(|script| script.instrs.len() == 6).unwrap();
+	assert_eq!(script.to_string(), "NOT A J\nNOT B T\nOR T J\nNOT C T\nOR T J\nAND D J\nWALK\n");
+	assert!(survives(script, &hull("#####.#..########")));
+	assert!(survives(script, &hull("#####...#########")));
+	assert!(!survives(&walk[0], &hull("#####.#..########")));
+}
+
+#[test]
+fn test_first_frame() {
+	// Prints two frames of the fall animation
+	let text = "Didn't make it across:\n\n@..\n#.#\n\n.@.\n#.#\n";
+	let mut program = text.bytes().flat_map(|c| vec![104, i64::from(c)]).collect::<Vec<_>>();
+	program.push(99);
+	let script = Script { instrs: Vec::new(), run: false };
+	assert_eq!(springdroid(&program, &script), Err("Didn't make it across:\n\n@..\n#.#\n".to_string()));
+}
+
+#[test]
+fn test2() {
+	// A fake springdroid on hidden hulls, failures render only the first frame
+	let hidden = [hull("#####.#.##...####"), hull("#####..#.########"), hull("#####.#.##.#.####")];
+	let mut attempts = 0;
+	let result = search(true, |script| {
+		attempts += 1;
+		match hidden.iter().find(|hull| !survives(script, hull)) {
+			Some(hull) => {
+				let row = hull.iter().map(|&ground| if ground { '#' } else { '.' }).collect::<String>();
+				Err(format!("Didn't make it across:\n\n.................\n@................\n{}\n", row))
+			},
+			None => Ok(42),
+		}
+	});
+	let (script, damage) = result.unwrap();
+	assert_eq!(damage, 42);
+	assert!(hidden.iter().all(|hull| survives(&script, hull)));
+	assert!(attempts < candidates(true).len());
+	assert!(search(false, |_| Err(String::new())).is_err());
+}
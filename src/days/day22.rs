@@ -0,0 +1,175 @@
+use std::io;
+use std::io::prelude::*;
+use crate::solution::Solution;
+
+pub fn main() {
+	let mut input = String::new();
+	let _ = io::stdin().read_to_string(&mut input).expect("error reading input");
+	let techniques = parse(&input);
+
+	let result1 = Linear::shuffle(&techniques, 10007).apply(2019);
+	print!("Part One\n========\n\nCard 2019 ends up at position `{}`.\n\n", result1);
+
+	let result2 = Linear::shuffle(&techniques, 119315717514047).pow(101741582076661).inverse().apply(2020);
+	print!("Part Two\n========\n\nThe card at position 2020 is `{}`.\n\n", result2);
+}
+
+pub struct Day22;
+impl Solution for Day22 {
+	const DAY: u32 = 22;
+	fn part_one(input: &str) -> String {
+		Linear::shuffle(&parse(input), 10007).apply(2019).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(Linear::shuffle(&parse(input), 119315717514047).pow(101741582076661).inverse().apply(2020).to_string())
+	}
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Technique {
+	NewStack,
+	Cut(i64),
+	Increment(i64),
+}
+
+fn parse(s: &str) -> Vec<Technique> {
+	s.lines().map(str::trim).filter(|line| !line.is_empty()).map(|line| {
+		if line == "deal into new stack" {
+			Technique::NewStack
+		}
+		else if let Some(n) = line.strip_prefix("cut ") {
+			Technique::Cut(n.parse().expect("error parsing cut"))
+		}
+		else if let Some(n) = line.strip_prefix("deal with increment ") {
+			Technique::Increment(n.parse().expect("error parsing increment"))
+		}
+		else {
+			panic!("unknown technique: {}", line)
+		}
+	}).collect()
+}
+
+/// The position of a card after shuffling as `(a * position + b) mod m`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Linear {
+	a: i128,
+	b: i128,
+	m: i128,
+}
+impl Linear {
+	fn identity(m: i64) -> Linear {
+		Linear { a: 1, b: 0, m: m as i128 }
+	}
+	fn technique(technique: Technique, m: i64) -> Linear {
+		let (a, b) = match technique {
+			Technique::NewStack => (-1, -1),
+			Technique::Cut(n) => (1, -n as i128),
+			Technique::Increment(n) => (n as i128, 0),
+		};
+		Linear { a, b, m: m as i128 }.normalize()
+	}
+	fn shuffle(techniques: &[Technique], m: i64) -> Linear {
+		techniques.iter().fold(Linear::identity(m), |f, &technique| f.then(Linear::technique(technique, m)))
+	}
+	fn normalize(self) -> Linear {
+		Linear { a: self.a.rem_euclid(self.m), b: self.b.rem_euclid(self.m), m: self.m }
+	}
+	fn apply(&self, x: i64) -> i64 {
+		((self.a * x as i128 + self.b).rem_euclid(self.m)) as i64
+	}
+	/// Applies `self` followed by `g`.
+	fn then(&self, g: Linear) -> Linear {
+		Linear { a: g.a * self.a, b: g.a * self.b + g.b, m: self.m }.normalize()
+	}
+	/// Repeats the shuffle `n` times by squaring.
+	fn pow(&self, mut n: u64) -> Linear {
+		let mut result = Linear::identity(self.m as i64);
+		let mut base = *self;
+		while n > 0 {
+			if n & 1 != 0 {
+				result = result.then(base);
+			}
+			base = base.then(base);
+			n >>= 1;
+		}
+		result
+	}
+	/// Maps positions back to cards, `a` must be coprime to the deck size.
+	fn inverse(&self) -> Linear {
+		let a = mod_inverse(self.a, self.m).expect("shuffle is not invertible");
+		Linear { a, b: -a * self.b, m: self.m }.normalize()
+	}
+}
+
+/// Extended Euclid.
+fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+	let (mut r0, mut r1) = (a.rem_euclid(m), m);
+	let (mut s0, mut s1) = (1i128, 0i128);
+	while r1 != 0 {
+		let q = r0 / r1;
+		r0 -= q * r1;
+		std::mem::swap(&mut r0, &mut r1);
+		s0 -= q * s1;
+		std::mem::swap(&mut s0, &mut s1);
+	}
+	if r0 == 1 { Some(s0.rem_euclid(m)) } else { None }
+}
+
+/// Shuffles an actual deck, returns the cards from top to bottom.
+#[cfg(test)]
+fn naive(techniques: &[Technique], deck: Vec<i64>) -> Vec<i64> {
+	techniques.iter().fold(deck, |deck, &technique| {
+		let len = deck.len() as i64;
+		match technique {
+			Technique::NewStack => deck.into_iter().rev().collect(),
+			Technique::Cut(n) => {
+				let n = n.rem_euclid(len) as usize;
+				deck[n..].iter().chain(&deck[..n]).cloned().collect()
+			},
+			Technique::Increment(n) => {
+				let mut table = vec![0; deck.len()];
+				for (i, card) in deck.into_iter().enumerate() {
+					table[(i as i64 * n % len) as usize] = card;
+				}
+				table
+			},
+		}
+	})
+}
+
+#[cfg(test)]
+const EXAMPLES: [(&str, [i64; 10]); 4] = [
+	("deal with increment 7\ndeal into new stack\ndeal into new stack", [0,3,6,9,2,5,8,1,4,7]),
+	("cut 6\ndeal with increment 7\ndeal into new stack", [3,0,7,4,1,8,5,2,9,6]),
+	("deal with increment 7\ndeal with increment 9\ncut -2", [6,3,0,7,4,1,8,5,2,9]),
+	("deal into new stack\ncut -2\ndeal with increment 7\ncut 8\ncut -4\ndeal with increment 7\ncut 3\ndeal with increment 9\ndeal with increment 3\ncut -1", [9,2,5,8,1,4,7,0,3,6]),
+];
+
+#[test]
+fn test1() {
+	for &(input, expected) in &EXAMPLES {
+		let techniques = parse(input);
+		assert_eq!(naive(&techniques, (0..10).collect()), expected);
+		let f = Linear::shuffle(&techniques, 10);
+		for (pos, &card) in expected.iter().enumerate() {
+			assert_eq!(f.apply(card), pos as i64);
+		}
+	}
+}
+
+#[test]
+fn test2() {
+	let techniques = parse(EXAMPLES[3].0);
+	// Deck sizes coprime to every increment
+	for &size in &[10, 11, 13] {
+		let f = Linear::shuffle(&techniques, size);
+		let mut deck = (0..size).collect::<Vec<_>>();
+		for n in 0..25 {
+			let card_at = f.pow(n).inverse();
+			assert_eq!((0..size).map(|pos| card_at.apply(pos)).collect::<Vec<_>>(), deck);
+			deck = naive(&techniques, deck);
+		}
+	}
+	assert_eq!(mod_inverse(6, 10), None);
+	assert_eq!(mod_inverse(7, 10), Some(3));
+}
@@ -0,0 +1,139 @@
+use crate::intcode::{load, Intcode, State};
+use crate::solution::Solution;
+
+const NAT: i64 = 255;
+
+pub fn main() {
+	let program = load::stdin::<i64>();
+
+	let result1 = first_nat_packet(&program, 50);
+	print!("Part One\n========\n\nThe first packet sent to address 255 has Y value `{}`.\n\n", result1);
+
+	let result2 = repeated_wakeup(&program, 50);
+	print!("Part Two\n========\n\nThe first Y value delivered by the NAT twice in a row is `{}`.\n\n", result2);
+}
+
+pub struct Day23;
+impl Solution for Day23 {
+	const DAY: u32 = 23;
+	fn part_one(input: &str) -> String {
+		first_nat_packet(&load::text::<i64>(input), 50).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(repeated_wakeup(&load::text::<i64>(input), 50).to_string())
+	}
+}
+
+/// A packet `(destination, X, Y)`.
+type Packet = (i64, i64, i64);
+
+struct Network {
+	nics: Vec<Intcode>,
+	/// Partial packets being output by every NIC.
+	outputs: Vec<Vec<i64>>,
+}
+impl Network {
+	fn boot(program: &[i64], size: usize) -> Network {
+		let nics = (0..size).map(|address| {
+			let mut nic = Intcode::new(program);
+			nic.input.push_back(address as i64);
+			nic
+		}).collect();
+		Network { nics, outputs: vec![Vec::new(); size] }
+	}
+	/// Runs every NIC until it waits for input, a NIC with an empty queue receives `-1`.
+	///
+	/// Returns the packets sent, packets to other NICs are already delivered.
+	fn round(&mut self) -> Vec<Packet> {
+		let mut sent = Vec::new();
+		for (nic, output) in self.nics.iter_mut().zip(&mut self.outputs) {
+			if nic.input.is_empty() {
+				nic.input.push_back(-1);
+			}
+			loop {
+				match nic.run().expect("NIC crashed") {
+					State::Running => (),
+					State::Output(value) => {
+						output.push(value);
+						if let [dest, x, y] = output[..] {
+							sent.push((dest, x, y));
+							output.clear();
+						}
+					},
+					State::Input | State::Halted => break,
+				}
+			}
+		}
+		for &(dest, x, y) in &sent {
+			if let Some(nic) = self.nics.get_mut(dest as usize) {
+				nic.input.extend(&[x, y]);
+			}
+		}
+		sent
+	}
+	/// Idle when no packets were sent and every NIC has read all its input.
+	fn idle(&self, sent: &[Packet]) -> bool {
+		sent.is_empty() && self.nics.iter().all(|nic| nic.input.is_empty())
+	}
+}
+
+fn first_nat_packet(program: &[i64], size: usize) -> i64 {
+	let mut network = Network::boot(program, size);
+	loop {
+		if let Some(&(_, _, y)) = network.round().iter().find(|packet| packet.0 == NAT) {
+			return y;
+		}
+	}
+}
+
+/// The NAT remembers the last packet sent to it and delivers it to address 0 whenever the network is idle.
+fn repeated_wakeup(program: &[i64], size: usize) -> i64 {
+	let mut network = Network::boot(program, size);
+	let mut nat = None;
+	let mut last_y = None;
+	loop {
+		let sent = network.round();
+		if let Some(&(_, x, y)) = sent.iter().rev().find(|packet| packet.0 == NAT) {
+			nat = Some((x, y));
+		}
+		if network.idle(&sent) {
+			let (x, y) = nat.expect("network idle before the NAT received a packet");
+			if last_y == Some(y) {
+				return y;
+			}
+			last_y = Some(y);
+			network.nics[0].input.extend(&[x, y]);
+		}
+	}
+}
+
+#[cfg(test)]
+fn chain() -> Vec<i64> {
+	use crate::intcode::compiler;
+	// Every NIC forwards packets to the next one incrementing Y up to 105, the last one to the NAT
+	compiler::compile("
+		fn main() {
+			let address = input();
+			if address == 0 { output(1); output(0); output(100); }
+			while 1 {
+				let x = input();
+				if x != -1 {
+					let y = input();
+					if y < 105 { y = y + 1; }
+					let dest = address + 1;
+					if dest == 4 { dest = 255; }
+					output(dest); output(x); output(y);
+				}
+			}
+		}").unwrap()
+}
+
+#[test]
+fn test1() {
+	assert_eq!(first_nat_packet(&chain(), 4), 103);
+}
+
+#[test]
+fn test2() {
+	assert_eq!(repeated_wakeup(&chain(), 4), 105);
+}
@@ -0,0 +1,124 @@
+use std::io;
+use std::io::prelude::*;
+use std::collections::VecDeque;
+use crate::automaton::Automaton;
+use crate::solution::Solution;
+
+pub fn main() {
+	let mut input = String::new();
+	let _ = io::stdin().read_to_string(&mut input).expect("error reading input");
+	let eris = eris();
+	let state = eris.parse(&input);
+
+	let result1 = eris.first_repeat(state);
+	print!("Part One\n========\n\nThe biodiversity rating of the first layout appearing twice is `{}`.\n\n", result1);
+
+	let result2 = Recursive::new(state).run(&eris, 200).bugs();
+	print!("Part Two\n========\n\nAfter 200 minutes there are `{}` bugs.\n\n", result2);
+}
+
+pub struct Day24;
+impl Solution for Day24 {
+	const DAY: u32 = 24;
+	fn part_one(input: &str) -> String {
+		let eris = eris();
+		eris.first_repeat(eris.parse(input)).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		let eris = eris();
+		Some(Recursive::new(eris.parse(input)).run(&eris, 200).bugs().to_string())
+	}
+}
+
+/// A bug dies unless there's exactly one bug adjacent, an empty tile becomes infested if one or two bugs are adjacent.
+fn eris() -> Automaton {
+	Automaton::von_neumann(5, 5, |bug, count| count == 1 || !bug && count == 2)
+}
+
+const CENTER: usize = 12;
+
+/// Neighbourhood masks of every tile on the level containing the grid, the same level and the level inside the center tile.
+fn recursive_neighbours() -> Vec<[u32; 3]> {
+	let eris = eris();
+	(0..25).map(|index| {
+		let (x, y) = (index as i32 % 5, index as i32 / 5);
+		let mut masks = [0u32, eris.neighbours[index] as u32 & !(1 << CENTER), 0u32];
+		for &(dx, dy) in &[(0, -1), (0, 1), (-1, 0), (1, 0)] {
+			let (nx, ny) = (x + dx, y + dy);
+			if !(0..5).contains(&nx) || !(0..5).contains(&ny) {
+				// The tile next to the center of the outer level
+				masks[0] |= 1 << ((2 + dy) * 5 + 2 + dx);
+			}
+			else if (ny * 5 + nx) as usize == CENTER {
+				// The edge of the inner level facing this tile
+				for i in 0..5 {
+					let (ix, iy) = match (dx, dy) {
+						(0, 1) => (i, 0),
+						(0, -1) => (i, 4),
+						(1, 0) => (0, i),
+						_ => (4, i),
+					};
+					masks[2] |= 1 << (iy * 5 + ix);
+				}
+			}
+		}
+		masks
+	}).collect()
+}
+
+/// Levels of nested grids from the outermost one with bugs.
+struct Recursive {
+	levels: VecDeque<u32>,
+}
+impl Recursive {
+	fn new(state: u64) -> Recursive {
+		Recursive { levels: vec![state as u32 & !(1 << CENTER)].into() }
+	}
+	fn bugs(&self) -> u32 {
+		self.levels.iter().map(|level| level.count_ones()).sum()
+	}
+	fn run(mut self, eris: &Automaton, minutes: usize) -> Recursive {
+		let neighbours = recursive_neighbours();
+		for _ in 0..minutes {
+			// Bugs spread at most one level further each minute
+			self.levels.push_front(0);
+			self.levels.push_back(0);
+			let level = |index: usize, offset: usize| (index + offset).checked_sub(1).and_then(|i| self.levels.get(i)).cloned().unwrap_or(0);
+			let next = (0..self.levels.len()).map(|index| {
+				(0..25).filter(|&tile| tile != CENTER).filter(|&tile| {
+					let count = (0..3).map(|offset| (level(index, offset) & neighbours[tile][offset]).count_ones()).sum();
+					eris.cell(level(index, 1) as u64, tile, count)
+				}).fold(0, |state, tile| state | 1 << tile)
+			}).collect::<VecDeque<u32>>();
+			self.levels = next;
+			while self.levels.len() > 1 && self.levels.front() == Some(&0) {
+				self.levels.pop_front();
+			}
+			while self.levels.len() > 1 && self.levels.back() == Some(&0) {
+				self.levels.pop_back();
+			}
+		}
+		self
+	}
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "....#\n#..#.\n#..##\n..#..\n#....\n";
+
+#[test]
+fn test1() {
+	let eris = eris();
+	let state = eris.parse(EXAMPLE);
+	assert_eq!(eris.render(eris.step(state)), "#..#.\n####.\n###.#\n##.##\n.##..\n");
+	let repeat = eris.first_repeat(state);
+	assert_eq!(eris.render(repeat), ".....\n.....\n.....\n#....\n.#...\n");
+	assert_eq!(repeat, 2129920);
+}
+
+#[test]
+fn test2() {
+	let eris = eris();
+	let recursive = Recursive::new(eris.parse(EXAMPLE)).run(&eris, 10);
+	assert_eq!(recursive.bugs(), 99);
+	assert_eq!(recursive.levels.len(), 11);
+}
@@ -0,0 +1,383 @@
+/*!
+Cryostasis text adventure.
+
+Usage: `cargo run --bin day25 < input` explores the ship automatically and finds the password.
+`cargo run --bin day25 -- --play <program>` plays the adventure interactively, commands are read from stdin.
+*/
+
+use std::{env, fs, io};
+use std::collections::{HashMap, VecDeque};
+use std::io::prelude::*;
+use crate::intcode::{load, Intcode, State};
+use crate::intcode::replay::Session;
+use crate::solution::Solution;
+
+pub fn main() {
+	let mut args = env::args().skip(1);
+	if let Some(arg) = args.next() {
+		assert_eq!(arg, "--play", "usage: day25 [--play <program>]");
+		let path = args.next().expect("missing program");
+		let program = load::load(&fs::read(&path).expect("error reading program")).unwrap_or_else(|err| panic!("error loading program: {}", err));
+		play(&program);
+		return;
+	}
+
+	let program = load::stdin::<i64>();
+	let (mut droid, intro) = Droid::boot(&program);
+	let ship = explore(&mut droid, &intro);
+	match unlock(&mut droid, &ship) {
+		Some((password, items)) => print!("Part One\n========\n\nThe password for the main airlock is `{}` carrying {}.\n\n", password, items.join(", ")),
+		None => print!("Part One\n========\n\nNo combination of the items {} gets past the checkpoint.\n\n", ship.inventory.join(", ")),
+	}
+}
+
+pub struct Day25;
+impl Solution for Day25 {
+	const DAY: u32 = 25;
+	fn part_one(input: &str) -> String {
+		let (mut droid, intro) = Droid::boot(&load::text::<i64>(input));
+		let ship = explore(&mut droid, &intro);
+		unlock(&mut droid, &ship).expect("no combination of items gets past the checkpoint").0.to_string()
+	}
+}
+
+/// Plays interactively, every line read is sent as a command.
+fn play(program: &[i64]) {
+	let stdin = io::stdin();
+	let mut lines = stdin.lock().lines();
+	let mut pending = VecDeque::new();
+	let mut session = Session::new(program).live(move || {
+		while pending.is_empty() {
+			let line = lines.next()?.expect("error reading input");
+			pending.extend(line.trim().bytes().map(i64::from));
+			pending.push_back(10);
+		}
+		pending.pop_front()
+	});
+	session.run(&mut |value| print!("{}", value as u8 as char)).expect("droid crashed");
+}
+
+/// The game's answer to a command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Reply {
+	/// Waiting for the next command.
+	Prompt(String),
+	/// The game is over.
+	Halted(String),
+	/// Didn't ask for another command in time.
+	Stuck,
+}
+
+/// Something to play the adventure against, cloned to try out dangerous commands.
+trait Game: Clone {
+	fn command(&mut self, command: &str) -> Reply;
+}
+
+/// Instructions to execute for a single command before the droid is considered stuck.
+const STEP_LIMIT: u64 = 1_000_000;
+
+#[derive(Clone, Debug)]
+struct Droid {
+	vm: Intcode,
+}
+impl Droid {
+	/// Returns the droid and the description of the first room.
+	fn boot(program: &[i64]) -> (Droid, String) {
+		let mut droid = Droid { vm: Intcode::new(program) };
+		match droid.run() {
+			Reply::Prompt(text) => (droid, text),
+			reply => panic!("droid didn't start: {:?}", reply),
+		}
+	}
+	fn run(&mut self) -> Reply {
+		let start = self.vm.steps;
+		let mut text = String::new();
+		loop {
+			match self.vm.step().expect("droid crashed") {
+				State::Running if self.vm.steps - start > STEP_LIMIT => return Reply::Stuck,
+				State::Running => (),
+				State::Output(value) => text.push(value as u8 as char),
+				State::Input => return Reply::Prompt(text),
+				State::Halted => return Reply::Halted(text),
+			}
+		}
+	}
+}
+impl Game for Droid {
+	fn command(&mut self, command: &str) -> Reply {
+		self.vm.input.extend(command.bytes().map(i64::from));
+		self.vm.input.push_back(10);
+		self.run()
+	}
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct Room {
+	name: String,
+	doors: Vec<String>,
+	items: Vec<String>,
+}
+
+/// Parses every room described in the text, being ejected from a room describes two rooms.
+fn parse_rooms(text: &str) -> Vec<Room> {
+	let mut rooms: Vec<Room> = Vec::new();
+	let mut list = None;
+	for line in text.lines().map(str::trim) {
+		if let Some(name) = line.strip_prefix("== ").and_then(|line| line.strip_suffix(" ==")) {
+			rooms.push(Room { name: name.to_string(), ..Room::default() });
+			list = None;
+		}
+		else if line == "Doors here lead:" || line == "Items here:" {
+			list = Some(line == "Items here:");
+		}
+		else if let (Some(entry), Some(room), Some(items)) = (line.strip_prefix("- "), rooms.last_mut(), list) {
+			if items { room.items.push(entry.to_string()) } else { room.doors.push(entry.to_string()) }
+		}
+		else if line.is_empty() {
+			list = None;
+		}
+	}
+	rooms
+}
+
+fn opposite(door: &str) -> &'static str {
+	match door {
+		"north" => "south",
+		"south" => "north",
+		"east" => "west",
+		"west" => "east",
+		_ => panic!("unknown door: {}", door),
+	}
+}
+
+#[derive(Clone, Debug, Default)]
+struct Ship {
+	start: String,
+	rooms: HashMap<String, Room>,
+	/// The room behind every explored door.
+	doors: HashMap<(String, String), String>,
+	/// The checkpoint and its door to the pressure-sensitive floor.
+	floor: Option<(String, String)>,
+	/// Items picked up along the way.
+	inventory: Vec<String>,
+}
+
+/// Takes the item on a copy of the game and checks the droid can still move afterwards.
+fn safe<G: Game>(game: &G, item: &str, door: &str) -> bool {
+	let mut trial = game.clone();
+	match trial.command(&format!("take {}", item)) {
+		Reply::Prompt(_) => matches!(trial.command(door), Reply::Prompt(text) if !parse_rooms(&text).is_empty()),
+		_ => false,
+	}
+}
+
+/// Maps the ship collecting every safe item, returns to the starting room.
+fn explore<G: Game>(game: &mut G, intro: &str) -> Ship {
+	let room = parse_rooms(intro).pop().expect("no room in the introduction");
+	let mut ship = Ship { start: room.name.clone(), ..Ship::default() };
+	visit(game, room, &mut ship);
+	ship
+}
+
+fn visit<G: Game>(game: &mut G, room: Room, ship: &mut Ship) {
+	let name = room.name.clone();
+	for item in &room.items {
+		if safe(game, item, &room.doors[0]) {
+			match game.command(&format!("take {}", item)) {
+				Reply::Prompt(_) => ship.inventory.push(item.clone()),
+				reply => panic!("taking {} failed: {:?}", item, reply),
+			}
+		}
+	}
+	ship.rooms.insert(name.clone(), room.clone());
+	for door in &room.doors {
+		if ship.doors.contains_key(&(name.clone(), door.clone())) {
+			continue;
+		}
+		let mut rooms = match game.command(door) {
+			Reply::Prompt(text) => parse_rooms(&text),
+			reply => panic!("moving {} from {} failed: {:?}", door, name, reply),
+		};
+		if rooms.len() > 1 {
+			// Ejected back by the pressure-sensitive floor
+			ship.floor = Some((name.clone(), door.clone()));
+			ship.doors.insert((name.clone(), door.clone()), name.clone());
+			continue;
+		}
+		let next = rooms.pop().expect("moved into no room");
+		ship.doors.insert((name.clone(), door.clone()), next.name.clone());
+		ship.doors.insert((next.name.clone(), opposite(door).to_string()), name.clone());
+		if !ship.rooms.contains_key(&next.name) {
+			visit(game, next, ship);
+		}
+		game.command(opposite(door));
+	}
+}
+
+/// Doors to go through from one room to another.
+fn path(ship: &Ship, from: &str, to: &str) -> Option<Vec<String>> {
+	let mut prev: HashMap<&str, (&str, &str)> = HashMap::new();
+	let mut queue = VecDeque::new();
+	queue.push_back(from);
+	while let Some(room) = queue.pop_front() {
+		if room == to {
+			let mut doors = Vec::new();
+			let mut room = to;
+			while room != from {
+				let (back, door) = prev[room];
+				doors.push(door.to_string());
+				room = back;
+			}
+			doors.reverse();
+			return Some(doors);
+		}
+		for ((src, door), dest) in &ship.doors {
+			if src == room && dest != from && !prev.contains_key(dest.as_str()) {
+				prev.insert(dest, (room, door));
+				queue.push_back(dest);
+			}
+		}
+	}
+	None
+}
+
+/// The number typed on the keypad in the final message.
+fn password(text: &str) -> Option<u64> {
+	let (_, rest) = text.split_once("typing ")?;
+	rest.split_whitespace().next()?.parse().ok()
+}
+
+/// Walks from the starting room to the checkpoint and tries item combinations in Gray code order.
+///
+/// Returns the password and the items carried.
+fn unlock<G: Game>(game: &mut G, ship: &Ship) -> Option<(u64, Vec<String>)> {
+	let (checkpoint, floor) = ship.floor.as_ref()?;
+	for door in path(ship, &ship.start, checkpoint)? {
+		game.command(&door);
+	}
+	let mut held = vec![true; ship.inventory.len()];
+	for combination in 1..=1u64 << ship.inventory.len() {
+		match game.command(floor) {
+			Reply::Halted(text) => {
+				let items = ship.inventory.iter().zip(&held).filter(|&(_, &held)| held).map(|(item, _)| item.clone()).collect();
+				return password(&text).map(|password| (password, items));
+			},
+			Reply::Prompt(_) => (),
+			Reply::Stuck => return None,
+		}
+		// Consecutive Gray codes differ in the lowest set bit of the counter
+		let index = combination.trailing_zeros() as usize;
+		if let Some(item) = ship.inventory.get(index) {
+			game.command(&format!("{} {}", if held[index] { "drop" } else { "take" }, item));
+			held[index] = !held[index];
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+fn describe(name: &str, doors: &[&str], items: &[String]) -> String {
+	let mut text = format!("\n\n\n== {} ==\nA room on the ship.\n\nDoors here lead:\n", name);
+	for door in doors {
+		text += &format!("- {}\n", door);
+	}
+	if !items.is_empty() {
+		text += "\nItems here:\n";
+		for item in items {
+			text += &format!("- {}\n", item);
+		}
+	}
+	text + "\nCommand?\n"
+}
+
+/// A small ship, the floor wants the mug and the book.
+#[cfg(test)]
+#[derive(Clone, Debug)]
+struct Fake {
+	room: usize,
+	items: Vec<Vec<String>>,
+	held: Vec<String>,
+}
+#[cfg(test)]
+const FAKE_ROOMS: [(&str, &[(&str, usize)]); 4] = [
+	("Hull Breach", &[("north", 1), ("east", 2)]),
+	("Kitchen", &[("north", 3), ("south", 0)]),
+	("Lab", &[("west", 0)]),
+	("Security Checkpoint", &[("south", 1), ("west", 4)]),
+];
+#[cfg(test)]
+impl Fake {
+	fn new() -> (Fake, String) {
+		let items = [&["mug", "infinite loop"][..], &["giant electromagnet", "coin", "molten lava", "book"], &[], &[]];
+		let fake = Fake { room: 0, items: items.iter().map(|items| items.iter().map(|item| item.to_string()).collect()).collect(), held: Vec::new() };
+		let intro = fake.look();
+		(fake, intro)
+	}
+	fn look(&self) -> String {
+		let (name, doors) = FAKE_ROOMS[self.room];
+		describe(name, &doors.iter().map(|door| door.0).collect::<Vec<_>>(), &self.items[self.room])
+	}
+}
+#[cfg(test)]
+impl Game for Fake {
+	fn command(&mut self, command: &str) -> Reply {
+		if let Some(item) = command.strip_prefix("take ") {
+			match item {
+				"infinite loop" => return Reply::Stuck,
+				"molten lava" => return Reply::Halted("\nThe molten lava is way too hot! You melt!\n".to_string()),
+				_ => (),
+			}
+			let index = self.items[self.room].iter().position(|i| i == item).expect("no such item");
+			self.held.push(self.items[self.room].remove(index));
+			return Reply::Prompt(format!("\nYou take the {}.\n\nCommand?\n", item));
+		}
+		if let Some(item) = command.strip_prefix("drop ") {
+			let index = self.held.iter().position(|i| i == item).expect("not carrying item");
+			self.items[self.room].push(self.held.remove(index));
+			return Reply::Prompt(format!("\nYou drop the {}.\n\nCommand?\n", item));
+		}
+		if self.held.iter().any(|item| item == "giant electromagnet") {
+			return Reply::Prompt("\nThe giant electromagnet is stuck to you.  You can't move!!\n\nCommand?\n".to_string());
+		}
+		let &(_, dest) = FAKE_ROOMS[self.room].1.iter().find(|door| door.0 == command).expect("no such door");
+		if dest < FAKE_ROOMS.len() {
+			self.room = dest;
+			return Reply::Prompt(self.look());
+		}
+		let floor = describe("Pressure-Sensitive Floor", &["east"], &[]).replace("\nCommand?\n", "");
+		let mut held = self.held.clone();
+		held.sort();
+		if held == ["book", "mug"] {
+			return Reply::Halted(floor + "\"Oh, hello! You should be able to get in by typing 1234 on the keypad at the main airlock.\"\n");
+		}
+		let weight = held.iter().map(|item| if item == "coin" { 10 } else { 1 }).sum::<i32>();
+		let alert = if weight > 2 { "lighter" } else { "heavier" };
+		Reply::Prompt(floor + &format!("Alert! Droids on this ship are {} than the detected value!\n", alert) + &self.look())
+	}
+}
+
+#[test]
+fn test1() {
+	let text = describe("Pressure-Sensitive Floor", &["east"], &[]) + &describe("Security Checkpoint", &["south", "west"], &["mug".to_string()]);
+	let rooms = parse_rooms(&text);
+	assert_eq!(rooms.len(), 2);
+	assert_eq!(rooms[1], Room { name: "Security Checkpoint".to_string(), doors: vec!["south".to_string(), "west".to_string()], items: vec!["mug".to_string()] });
+	assert_eq!(password("You should be able to get in by typing 2622472 on the keypad"), Some(2622472));
+	assert_eq!(opposite("east"), "west");
+}
+
+#[test]
+fn test2() {
+	let (mut fake, intro) = Fake::new();
+	let ship = explore(&mut fake, &intro);
+	assert_eq!(ship.rooms.len(), 4);
+	assert_eq!(ship.floor, Some(("Security Checkpoint".to_string(), "west".to_string())));
+	let mut inventory = ship.inventory.clone();
+	inventory.sort();
+	assert_eq!(inventory, ["book", "coin", "mug"]);
+	assert_eq!(fake.room, 0);
+	assert_eq!(path(&ship, "Lab", "Security Checkpoint"), Some(vec!["west".to_string(), "north".to_string(), "north".to_string()]));
+	let (password, mut items) = unlock(&mut fake, &ship).unwrap();
+	items.sort();
+	assert_eq!((password, items), (1234, vec!["book".to_string(), "mug".to_string()]));
+}
@@ -0,0 +1,47 @@
+use crate::intcode::load;
+use crate::intcode::backend::{self, Backend, Status};
+use crate::solution::Solution;
+
+pub fn main() {
+	let program = load::stdin::<i64>();
+
+	let result1 = execute(&program, 1);
+	print!("Part One\n========\n\nThe computer prints the diagnostic value `{}`.\n\n", result1);
+
+	let result2 = execute(&program, 5);
+	print!("Part Two\n========\n\nThe computer prints the diagnostic value `{}`.\n\n", result2);
+}
+
+pub struct Day5;
+impl Solution for Day5 {
+	const DAY: u32 = 5;
+	fn part_one(input: &str) -> String {
+		execute(&load::text::<i64>(input), 1).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(execute(&load::text::<i64>(input), 5).to_string())
+	}
+}
+
+fn execute(program: &[i64], input: i64) -> i64 {
+	let outcome = backend::Day5.run(program, &[input], u64::MAX);
+	match outcome.status {
+		Status::Halted => outcome.output.last().cloned().unwrap_or(0),
+		Status::Fault(fault) => panic!("{}", fault),
+		status => panic!("program stopped: {:?}", status),
+	}
+}
+
+#[test]
+fn testing() {
+	let intcode = [3,0,4,0,99];
+	let result = execute(&intcode, 42);
+	assert_eq!(result, 42);
+}
+
+#[test]
+fn testingfd() {
+	let intcode = [3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99];
+	let result = execute(&intcode, 8);
+	assert_eq!(result, 1000);
+}
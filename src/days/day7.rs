@@ -0,0 +1,76 @@
+use crate::intcode::{self, load, Intcode, State};
+use itertools::Itertools;
+use crate::solution::Solution;
+
+pub fn main() {
+	let program = load::stdin::<i64>();
+
+	let result1 = part_one(&program);
+	print!("Part One\n========\n\nThe highest signal sent to the thrusters is `{}`.\n\n", result1);
+
+	let result2 = part_two(&program);
+	print!("Part Two\n========\n\nThe highest signal with a feedback loop is `{}`.\n\n", result2);
+}
+
+pub struct Day7;
+impl Solution for Day7 {
+	const DAY: u32 = 7;
+	fn part_one(input: &str) -> String {
+		part_one(&load::text::<i64>(input)).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(part_two(&load::text::<i64>(input)).to_string())
+	}
+}
+
+/// Runs the amplifiers one after another, returns the last signal.
+fn chain(program: &[i64], phases: &[i64]) -> i64 {
+	phases.iter().fold(0, |signal, &phase| {
+		let output = intcode::execute(program, &[phase, signal]).expect("amplifier crashed");
+		*output.last().expect("amplifier without output")
+	})
+}
+
+fn part_one(program: &[i64]) -> i64 {
+	(0..5).permutations(5).map(|phases| chain(program, &phases)).max().unwrap()
+}
+
+fn part_two(program: &[i64]) -> i64 {
+	(5..=9).permutations(5).map(|phases| feedback(program, &phases)).max().unwrap()
+}
+
+/// Runs the amplifiers in a feedback loop until they halt, returns the last signal.
+fn feedback(program: &[i64], phases: &[i64]) -> i64 {
+	let mut amps = phases.iter().map(|&phase| {
+		let mut amp = Intcode::new(program);
+		amp.input.push_back(phase);
+		amp
+	}).collect::<Vec<_>>();
+	let mut signal = 0;
+	loop {
+		for amp in &mut amps {
+			amp.input.push_back(signal);
+			match amp.run().expect("amplifier crashed") {
+				State::Output(value) => signal = value,
+				State::Halted => return signal,
+				state => panic!("unexpected amplifier state: {:?}", state),
+			}
+		}
+	}
+}
+
+#[test]
+fn test1() {
+	let program = [3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0];
+	assert_eq!(chain(&program, &[4,3,2,1,0]), 43210);
+	assert_eq!(part_one(&program), 43210);
+	let program = [3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0];
+	assert_eq!(part_one(&program), 65210);
+}
+
+#[test]
+fn test2() {
+	let program = [3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5];
+	assert_eq!(feedback(&program, &[9,8,7,6,5]), 139629729);
+	assert_eq!(part_two(&program), 139629729);
+}
@@ -0,0 +1,72 @@
+use std::io;
+use std::io::prelude::*;
+use crate::solution::Solution;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+const SIZE: usize = WIDTH * HEIGHT;
+
+fn parse(s: &str) -> Vec<u8> {
+	s.bytes().collect()
+}
+
+pub fn main() {
+	let mut input = String::new();
+	let _ = io::stdin().read_to_string(&mut input).expect("error reading input");
+	let pixels = parse(&input);
+
+	println!("Part One\n========\n\nThe number is `{}`.", checksum(&pixels));
+
+	println!("Part Two\n========\n\n```\n");
+	print!("{}", decode(&pixels));
+	println!("```\n");
+}
+
+pub struct Day8;
+impl Solution for Day8 {
+	const DAY: u32 = 8;
+	fn part_one(input: &str) -> String {
+		checksum(&parse(input)).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(decode(&parse(input)))
+	}
+}
+
+/// Number of 1 digits times the number of 2 digits in the layer with the fewest 0 digits.
+fn checksum(pixels: &[u8]) -> usize {
+	let num_layers = pixels.len() / SIZE;
+	let mut least_zeroes = 9999999;
+	let mut checksum = 0;
+	for i in 0..num_layers {
+		let layer = &pixels[i * SIZE..(i + 1) * SIZE];
+		let zeroes = layer.iter().filter(|&&x| x == b'0').count();
+		if zeroes < least_zeroes {
+			least_zeroes = zeroes;
+			let ones = layer.iter().filter(|&&x| x == b'1').count();
+			let twos = layer.iter().filter(|&&x| x == b'2').count();
+			checksum = ones * twos;
+		}
+	}
+	checksum
+}
+
+/// Stacks the layers, white pixels are drawn as `#`.
+fn decode(pixels: &[u8]) -> String {
+	let num_layers = pixels.len() / SIZE;
+	let mut image = String::new();
+	for y in 0..HEIGHT {
+		for x in 0..WIDTH {
+			for i in 0..num_layers {
+				let layer = &pixels[i * SIZE..(i + 1) * SIZE];
+				let pixel = layer[y * WIDTH + x];
+				if pixel != b'2' {
+					image.push(if pixel == b'1' { '#' } else { ' ' });
+					break;
+				}
+			}
+		}
+		image.push('\n');
+	}
+	image
+}
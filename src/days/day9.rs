@@ -0,0 +1,52 @@
+use crate::intcode::load;
+use crate::intcode::backend::{self, Backend, Status};
+use crate::solution::Solution;
+
+pub fn main() {
+	let program = load::stdin::<i64>();
+
+	let result1 = execute(&program, 1);
+	print!("Part One\n========\n\nThe computer produces the BOOST keycode `{}`.\n\n", result1);
+
+	let result2 = execute(&program, 2);
+	print!("Part Two\n========\n\nThe coordinates of the distress signal is `{}`.\n\n", result2);
+}
+
+pub struct Day9;
+impl Solution for Day9 {
+	const DAY: u32 = 9;
+	fn part_one(input: &str) -> String {
+		execute(&load::text::<i64>(input), 1).to_string()
+	}
+	fn part_two(input: &str) -> Option<String> {
+		Some(execute(&load::text::<i64>(input), 2).to_string())
+	}
+}
+
+fn execute(program: &[i64], input: i64) -> i64 {
+	let outcome = backend::Day9.run(program, &[input], u64::MAX);
+	match outcome.status {
+		Status::Halted => outcome.output.last().cloned().unwrap_or(0),
+		Status::Fault(fault) => panic!("{}", fault),
+		status => panic!("program stopped: {:?}", status),
+	}
+}
+
+#[test]
+fn test1a() {
+	let program = [109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99];
+	let output = execute(&program, 0);
+	assert_eq!(output, 99); // Outputs a copy of itself, the last value being the halt opcode
+}
+#[test]
+fn test2a() {
+	let program = [1102,34915192,34915192,7,4,7,99,0];
+	let output = execute(&program, 0);
+	assert_eq!(output.to_string().len(), 16); // Output 16 digit number
+}
+#[test]
+fn test3a() {
+	let program = [104,1125899906842624,99];
+	let output = execute(&program, 0);
+	assert_eq!(output, 1125899906842624); // Output the large number in the middle
+}
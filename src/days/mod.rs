@@ -0,0 +1,47 @@
+/*!
+The solutions of every day.
+
+Each day has a `main` printing its answers to the input read from stdin, used by the `dayX` binaries, and implements [`Solution`](crate::solution::Solution) for the `aoc` runner.
+Declaring a day in the `days!` list below registers it with the runner.
+*/
+
+use crate::solution::Entry;
+
+macro_rules! days {
+	($($day:ident: $solution:ident,)*) => {
+		$(pub mod $day;)*
+
+		/// The registered solutions in order of their day.
+		pub fn all() -> Vec<Entry> {
+			vec![$(Entry::of::<$day::$solution>()),*]
+		}
+	};
+}
+
+days! {
+	day1: Day1,
+	day2: Day2,
+	day3: Day3,
+	day4: Day4,
+	day5: Day5,
+	day6: Day6,
+	day7: Day7,
+	day8: Day8,
+	day9: Day9,
+	day10: Day10,
+	day11: Day11,
+	day12: Day12,
+	day13: Day13,
+	day14: Day14,
+	day15: Day15,
+	day16: Day16,
+	day17: Day17,
+	day18: Day18,
+	day19: Day19,
+	day20: Day20,
+	day21: Day21,
+	day22: Day22,
+	day23: Day23,
+	day24: Day24,
+	day25: Day25,
+}
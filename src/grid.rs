@@ -0,0 +1,85 @@
+/*!
+Character grids.
+
+The grid reinterprets the puzzle input directly: rows are `stride` bytes apart, which skips the line endings.
+*/
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+	pub data: T,
+	pub stride: i32,
+	pub width: i32,
+	pub height: i32,
+}
+impl<'a> Grid<&'a [u8]> {
+	/// Computes the stride, width and height from the first line of the input.
+	pub fn parse(data: &'a [u8]) -> Grid<&'a [u8]> {
+		let width = data.iter().position(|&c| c == b'\r' || c == b'\n').unwrap_or(data.len());
+		let stride = data[width..].iter().take_while(|&&c| c == b'\r' || c == b'\n').count() + width;
+		let height = (data.len() + stride - width).checked_div(stride).unwrap_or(0);
+		Grid { data, stride: stride as i32, width: width as i32, height: height as i32 }
+	}
+}
+impl Grid<Vec<u8>> {
+	/// Builds the grid from lines of different lengths, padding them to the longest line.
+	pub fn from_lines(text: &str, padding: u8) -> Grid<Vec<u8>> {
+		let lines = text.lines().map(|line| line.trim_end_matches('\r').as_bytes()).collect::<Vec<_>>();
+		let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+		let mut data = Vec::with_capacity(width * lines.len());
+		for line in &lines {
+			data.extend_from_slice(line);
+			data.resize(data.len() + width - line.len(), padding);
+		}
+		Grid { data, stride: width as i32, width: width as i32, height: lines.len() as i32 }
+	}
+}
+impl<T: AsRef<[u8]>> Grid<T> {
+	fn index(&self, (x, y): (i32, i32)) -> Option<usize> {
+		if x < 0 || x >= self.width || y < 0 || y >= self.height {
+			return None;
+		}
+		Some((y * self.stride + x) as usize)
+	}
+	/// Returns the cell at the given `(x, y)` coordinate or `None` outside the grid.
+	pub fn get(&self, pos: (i32, i32)) -> Option<u8> {
+		self.index(pos).and_then(|index| self.data.as_ref().get(index).cloned())
+	}
+	/// Iterates over the coordinates and cells row by row.
+	pub fn cells(&self) -> impl Iterator<Item = ((i32, i32), u8)> + '_ {
+		(0..self.height).flat_map(move |y| (0..self.width).map(move |x| (x, y)))
+			.filter_map(move |pos| self.get(pos).map(|cell| (pos, cell)))
+	}
+	/// Returns the coordinate of the first cell with the value.
+	pub fn find(&self, value: u8) -> Option<(i32, i32)> {
+		self.cells().find(|&(_, cell)| cell == value).map(|(pos, _)| pos)
+	}
+	/// Copies the grid so it can be modified.
+	pub fn to_owned(&self) -> Grid<Vec<u8>> {
+		Grid { data: self.data.as_ref().to_vec(), stride: self.stride, width: self.width, height: self.height }
+	}
+}
+impl<T: AsRef<[u8]> + AsMut<[u8]>> Grid<T> {
+	/// Sets the cell, panics outside the grid.
+	pub fn set(&mut self, pos: (i32, i32), value: u8) {
+		let index = self.index(pos).expect("position outside the grid");
+		self.data.as_mut()[index] = value;
+	}
+}
+
+#[test]
+fn test_parse() {
+	let grid = Grid::parse(b"#.#\n.@.\n");
+	assert_eq!((grid.stride, grid.width, grid.height), (4, 3, 2));
+	let grid = Grid::parse(b"#.#\r\n.@.\r\n#..");
+	assert_eq!((grid.stride, grid.width, grid.height), (5, 3, 3));
+	assert_eq!(grid.find(b'@'), Some((1, 1)));
+	assert_eq!((grid.get((2, 2)), grid.get((3, 0)), grid.get((0, -1))), (Some(b'.'), None, None));
+
+	let mut grid = grid.to_owned();
+	grid.set((1, 1), b'#');
+	assert_eq!(grid.cells().filter(|&(_, cell)| cell == b'#').count(), 4);
+
+	let grid = Grid::from_lines("  A\r\n#.\n\n", b' ');
+	assert_eq!((grid.stride, grid.width, grid.height), (3, 3, 3));
+	assert_eq!(grid.data, b"  A#.    ");
+}
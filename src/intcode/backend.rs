@@ -0,0 +1,359 @@
+/*!
+Intcode backends.
+
+Over the days the Intcode computer grew from the day 2 interpreter with only addition and multiplication on `u32` cells,
+to the day 5 (and day 7) interpreter with input/output, jumps and immediate mode on `i32` cells,
+to the complete day 9 instruction set with relative mode on `i64` cells.
+
+The older interpreters are ported here behind a common interface so they can be checked against each other.
+The day 2, 5 and 9 solutions run on these backends, so the fuzzed interpreters are the ones which solve the puzzles.
+*/
+
+use std::convert::TryFrom;
+use super::{Fault, Intcode, State};
+
+/// Instruction set revisions, each one is a superset of the previous.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Level {
+	/// Add, multiply and halt in position mode.
+	Day2,
+	/// Input, output, jumps and comparisons, immediate mode.
+	Day5,
+	/// Relative mode and adjusting the relative base.
+	Day9,
+}
+impl Level {
+	/// Opcodes, without modes, of the instructions added up to this level except halt.
+	pub fn opcodes(&self) -> &'static [i64] {
+		match self {
+			Level::Day2 => &[1, 2],
+			Level::Day5 => &[1, 2, 3, 4, 5, 6, 7, 8],
+			Level::Day9 => &[1, 2, 3, 4, 5, 6, 7, 8, 9],
+		}
+	}
+	/// Number of parameter modes, position mode is `0`.
+	pub fn modes(&self) -> i64 {
+		match self {
+			Level::Day2 => 1,
+			Level::Day5 => 2,
+			Level::Day9 => 3,
+		}
+	}
+	/// Returns if the opcode only uses instructions and parameter modes of this level.
+	pub fn supports(&self, opcode: i64) -> bool {
+		// Without parameter modes the whole opcode is the instruction
+		if *self == Level::Day2 {
+			return opcode == 99 || self.opcodes().contains(&opcode);
+		}
+		let arity = match opcode % 100 {
+			99 => 0,
+			op if self.opcodes().contains(&op) => match op { 1 | 2 | 7 | 8 => 3, 5 | 6 => 2, _ => 1 },
+			_ => return false,
+		};
+		opcode >= 0 && (1..=arity).all(|n| opcode / [100, 1000, 10000][n - 1] % 10 < self.modes())
+	}
+}
+
+/// Describes what programs a backend can run.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Features {
+	pub level: Level,
+	/// Smallest value a memory cell can hold.
+	pub min: i64,
+	/// Largest value a memory cell can hold.
+	pub max: i64,
+	/// Memory grows when accessed beyond the program image.
+	pub growable: bool,
+}
+impl Features {
+	/// Returns if every program supported by `other` is supported by these features.
+	pub fn covers(&self, other: &Features) -> bool {
+		self.level >= other.level && self.min <= other.min && self.max >= other.max && (self.growable || !other.growable)
+	}
+	/// Returns if the fault is caused by the limits of these features rather than by the program.
+	///
+	/// A backend with wider features may run the program just fine.
+	/// Invalid opcodes and modes are only limits when the opcode is outside the instruction set of this level.
+	pub fn is_limit(&self, fault: &Fault) -> bool {
+		match *fault {
+			Fault::InvalidOpcode { opcode, .. } | Fault::InvalidMode { opcode, .. } => !self.level.supports(opcode),
+			Fault::OutOfMemory { .. } => !self.growable,
+			Fault::Overflow { .. } => self.min > i64::MIN || self.max < i64::MAX,
+			_ => false,
+		}
+	}
+}
+
+/// How a run ended.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+	Halted,
+	OutOfBudget,
+	OutOfInput,
+	Fault(Fault),
+}
+
+/// Everything observable after running a program.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Outcome {
+	pub status: Status,
+	pub output: Vec<i64>,
+	/// Final memory with trailing zeroes trimmed.
+	pub memory: Vec<i64>,
+}
+impl Outcome {
+	fn new(status: Status, output: Vec<i64>, mut memory: Vec<i64>) -> Outcome {
+		while memory.last() == Some(&0) {
+			memory.pop();
+		}
+		Outcome { status, output, memory }
+	}
+}
+
+pub trait Backend {
+	fn name(&self) -> &str;
+	fn features(&self) -> Features;
+	/// Runs the program with the given input for at most `budget` instructions.
+	fn run(&self, program: &[i64], input: &[i64], budget: u64) -> Outcome;
+}
+
+/// Returns all the built-in backends.
+pub fn backends() -> Vec<Box<dyn Backend>> {
+	vec![Box::new(Day2), Box::new(Day5), Box::new(Day9)]
+}
+
+//----------------------------------------------------------------
+
+/// The day 2 interpreter.
+pub struct Day2;
+impl Backend for Day2 {
+	fn name(&self) -> &str {
+		"day2"
+	}
+	fn features(&self) -> Features {
+		Features { level: Level::Day2, min: 0, max: u32::MAX as i64, growable: false }
+	}
+	fn run(&self, program: &[i64], _input: &[i64], budget: u64) -> Outcome {
+		let mut memory = Vec::with_capacity(program.len());
+		for &value in program {
+			match u32::try_from(value) {
+				Ok(value) => memory.push(value),
+				Err(_) => return Outcome::new(Status::Fault(Fault::Overflow { ip: 0 }), Vec::new(), program.to_vec()),
+			}
+		}
+		let status = match day2_execute(&mut memory, budget) {
+			Ok(status) => status,
+			Err(fault) => Status::Fault(fault),
+		};
+		Outcome::new(status, Vec::new(), memory.into_iter().map(i64::from).collect())
+	}
+}
+
+/// Runs the day 2 program in place for at most `budget` instructions.
+pub fn day2_execute(program: &mut [u32], budget: u64) -> Result<Status, Fault> {
+	let fetch = |program: &[u32], ip: usize, address: usize| {
+		program.get(address).cloned().ok_or(Fault::OutOfMemory { ip, address: address as i64 })
+	};
+	let mut ip = 0;
+	for _ in 0..budget {
+		match fetch(program, ip, ip)? {
+			opcode @ 1 | opcode @ 2 => {
+				let a = fetch(program, ip, ip + 1)? as usize;
+				let b = fetch(program, ip, ip + 2)? as usize;
+				let c = fetch(program, ip, ip + 3)? as usize;
+				let a = fetch(program, ip, a)?;
+				let b = fetch(program, ip, b)?;
+				let value = if opcode == 1 { a.checked_add(b) } else { a.checked_mul(b) };
+				let value = value.ok_or(Fault::Overflow { ip })?;
+				*program.get_mut(c).ok_or(Fault::OutOfMemory { ip, address: c as i64 })? = value;
+				ip += 4;
+			},
+			99 => return Ok(Status::Halted),
+			opcode => return Err(Fault::InvalidOpcode { ip, opcode: opcode as i64 }),
+		}
+	}
+	Ok(Status::OutOfBudget)
+}
+
+//----------------------------------------------------------------
+
+/// The day 5 and day 7 interpreter.
+pub struct Day5;
+impl Backend for Day5 {
+	fn name(&self) -> &str {
+		"day5"
+	}
+	fn features(&self) -> Features {
+		Features { level: Level::Day5, min: i32::MIN as i64, max: i32::MAX as i64, growable: false }
+	}
+	fn run(&self, program: &[i64], input: &[i64], budget: u64) -> Outcome {
+		let convert = |values: &[i64]| values.iter().map(|&value| i32::try_from(value)).collect::<Result<Vec<i32>, _>>();
+		let (mut memory, input) = match (convert(program), convert(input)) {
+			(Ok(memory), Ok(input)) => (memory, input),
+			_ => return Outcome::new(Status::Fault(Fault::Overflow { ip: 0 }), Vec::new(), program.to_vec()),
+		};
+		let mut output = Vec::new();
+		let status = match day5_execute(&mut memory, &input, &mut output, budget) {
+			Ok(status) => status,
+			Err(fault) => Status::Fault(fault),
+		};
+		let output = output.into_iter().map(i64::from).collect();
+		Outcome::new(status, output, memory.into_iter().map(i64::from).collect())
+	}
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Arg5 {
+	Position(i32),
+	Immediate(i32),
+}
+
+fn day5_execute(intcode: &mut [i32], inputs: &[i32], output: &mut Vec<i32>, budget: u64) -> Result<Status, Fault> {
+	fn fetch(intcode: &[i32], ip: usize, address: usize) -> Result<i32, Fault> {
+		intcode.get(address).cloned().ok_or(Fault::OutOfMemory { ip, address: address as i64 })
+	}
+	fn arg(intcode: &[i32], ip: usize, n: usize) -> Result<Arg5, Fault> {
+		let opcode = fetch(intcode, ip, ip)?;
+		let arg = fetch(intcode, ip, ip + n)?;
+		match opcode / [100, 1000, 10000][n - 1] % 10 {
+			0 => Ok(Arg5::Position(arg)),
+			1 => Ok(Arg5::Immediate(arg)),
+			_ => Err(Fault::InvalidMode { ip, opcode: opcode as i64 }),
+		}
+	}
+	fn address(ip: usize, address: i32) -> Result<usize, Fault> {
+		if address < 0 {
+			return Err(Fault::InvalidAddress { ip, address: address as i64 });
+		}
+		Ok(address as usize)
+	}
+	fn read(intcode: &[i32], ip: usize, arg: Arg5) -> Result<i32, Fault> {
+		match arg {
+			Arg5::Position(arg) => fetch(intcode, ip, address(ip, arg)?),
+			Arg5::Immediate(arg) => Ok(arg),
+		}
+	}
+	fn write(intcode: &mut [i32], ip: usize, arg: Arg5, value: i32) -> Result<(), Fault> {
+		match arg {
+			Arg5::Position(arg) => {
+				let address = address(ip, arg)?;
+				*intcode.get_mut(address).ok_or(Fault::OutOfMemory { ip, address: address as i64 })? = value;
+				Ok(())
+			},
+			Arg5::Immediate(_) => Err(Fault::ImmediateWrite { ip }),
+		}
+	}
+
+	let mut input = 0;
+	let mut ip = 0;
+	for _ in 0..budget {
+		let opcode = fetch(intcode, ip, ip)?;
+		match opcode % 100 {
+			1 | 2 | 7 | 8 => {
+				let (src1, src2, dest) = (arg(intcode, ip, 1)?, arg(intcode, ip, 2)?, arg(intcode, ip, 3)?);
+				let src1 = read(intcode, ip, src1)?;
+				let src2 = read(intcode, ip, src2)?;
+				let value = match opcode % 100 {
+					1 => src1.checked_add(src2).ok_or(Fault::Overflow { ip })?,
+					2 => src1.checked_mul(src2).ok_or(Fault::Overflow { ip })?,
+					7 => (src1 < src2) as i32,
+					_ => (src1 == src2) as i32,
+				};
+				write(intcode, ip, dest, value)?;
+				ip += 4;
+			},
+			3 => {
+				let dest = arg(intcode, ip, 1)?;
+				let value = match inputs.get(input) {
+					Some(&value) => value,
+					None => return Ok(Status::OutOfInput),
+				};
+				write(intcode, ip, dest, value)?;
+				input += 1;
+				ip += 2;
+			},
+			4 => {
+				let value = read(intcode, ip, arg(intcode, ip, 1)?)?;
+				output.push(value);
+				ip += 2;
+			},
+			5 | 6 => {
+				let (value, target) = (arg(intcode, ip, 1)?, arg(intcode, ip, 2)?);
+				let value = read(intcode, ip, value)?;
+				if (value != 0) == (opcode % 100 == 5) {
+					ip = address(ip, read(intcode, ip, target)?)?;
+				}
+				else {
+					ip += 3;
+				}
+			},
+			99 => return Ok(Status::Halted),
+			_ => return Err(Fault::InvalidOpcode { ip, opcode: opcode as i64 }),
+		}
+	}
+	Ok(Status::OutOfBudget)
+}
+
+//----------------------------------------------------------------
+
+/// The day 9 instruction set on the resumable [`Intcode`](../struct.Intcode.html) machine.
+pub struct Day9;
+impl Backend for Day9 {
+	fn name(&self) -> &str {
+		"day9"
+	}
+	fn features(&self) -> Features {
+		Features { level: Level::Day9, min: i64::MIN, max: i64::MAX, growable: true }
+	}
+	fn run(&self, program: &[i64], input: &[i64], budget: u64) -> Outcome {
+		let mut vm = Intcode::new(program);
+		vm.input.extend(input);
+		let mut output = Vec::new();
+		let status = loop {
+			if vm.steps >= budget {
+				break Status::OutOfBudget;
+			}
+			match vm.step() {
+				Ok(State::Running) => (),
+				Ok(State::Input) => break Status::OutOfInput,
+				Ok(State::Output(value)) => output.push(value),
+				Ok(State::Halted) => break Status::Halted,
+				Err(fault) => break Status::Fault(fault),
+			}
+		};
+		Outcome::new(status, output, vm.mem)
+	}
+}
+
+#[test]
+fn test_agree() {
+	// Day 5 compare with 8 example, exercises every backend except day 2
+	let program = [3,9,8,9,10,9,4,9,99,-1,8];
+	for backend in &backends()[1..] {
+		let outcome = backend.run(&program, &[8], 100);
+		assert_eq!(outcome.status, Status::Halted, "{}", backend.name());
+		assert_eq!(outcome.output, [1], "{}", backend.name());
+	}
+	let program = [1,1,1,4,99,5,6,0,99];
+	for backend in &backends() {
+		let outcome = backend.run(&program, &[], 100);
+		assert_eq!(outcome.memory, [30,1,1,4,2,5,6,0,99], "{}", backend.name());
+	}
+}
+
+#[test]
+fn test_supports() {
+	assert!(Level::Day2.supports(1) && Level::Day2.supports(99));
+	assert!(!Level::Day2.supports(1001) && !Level::Day2.supports(199) && !Level::Day2.supports(3));
+	assert!(Level::Day5.supports(1108) && !Level::Day5.supports(9) && !Level::Day5.supports(204));
+	assert!(Level::Day9.supports(21209) && Level::Day9.supports(10004) && Level::Day9.supports(1599));
+	assert!(!Level::Day9.supports(301) && !Level::Day9.supports(-1) && !Level::Day9.supports(42));
+}
+
+#[test]
+fn test_budget() {
+	let program = [1105,1,0];
+	for backend in &backends()[1..] {
+		assert_eq!(backend.run(&program, &[], 10).status, Status::OutOfBudget, "{}", backend.name());
+	}
+}
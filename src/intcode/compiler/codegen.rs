@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use crate::intcode::{Arg, Instr};
+use super::Error;
+use super::parser::{BinOp, Expr, Function, Stmt, StmtKind, UnOp};
+
+/// Value of a cell only known after more code has been generated.
+#[derive(Copy, Clone, Debug)]
+enum Patch {
+	/// Address of a label.
+	Label(usize),
+	/// Frame size of the current function plus an offset.
+	Frame(i64),
+	/// Negated frame size of the current function.
+	NegFrame,
+}
+
+const BUILTINS: &[(&str, usize)] = &[("input", 0), ("output", 1)];
+
+#[derive(Default)]
+struct Codegen<'a> {
+	code: Vec<i64>,
+	labels: Vec<Option<usize>>,
+	label_patches: Vec<(usize, usize)>,
+	/// Label and number of parameters of every function.
+	functions: HashMap<&'a str, (usize, usize)>,
+
+	// State of the function being generated
+	frame_patches: Vec<(usize, Patch)>,
+	scopes: Vec<HashMap<&'a str, i64>>,
+	loops: Vec<(usize, usize)>,
+	next_local: i64,
+	temp_base: i64,
+	next_temp: i64,
+	frame: i64,
+}
+impl<'a> Codegen<'a> {
+	fn label(&mut self) -> usize {
+		self.labels.push(None);
+		self.labels.len() - 1
+	}
+	fn bind(&mut self, label: usize) {
+		self.labels[label] = Some(self.code.len());
+	}
+	/// Emits an instruction, replacing the given parameters by patches.
+	fn emit(&mut self, instr: Instr, patches: &[(usize, Patch)]) {
+		let start = self.code.len() + 1;
+		instr.encode(&mut self.code);
+		for &(index, patch) in patches {
+			match patch {
+				Patch::Label(label) => self.label_patches.push((start + index, label)),
+				_ => self.frame_patches.push((start + index, patch)),
+			}
+		}
+	}
+	fn mov(&mut self, src: Arg, dest: Arg) {
+		self.emit(Instr::Add { src1: src, src2: Arg::Immediate(0), dest }, &[]);
+	}
+	fn jump(&mut self, label: usize) {
+		self.emit(Instr::JumpIfTrue { arg: Arg::Immediate(1), target: Arg::Immediate(0) }, &[(1, Patch::Label(label))]);
+	}
+	fn jump_if_false(&mut self, arg: Arg, label: usize) {
+		self.emit(Instr::JumpIfFalse { arg, target: Arg::Immediate(0) }, &[(1, Patch::Label(label))]);
+	}
+	fn temp(&mut self) -> Arg {
+		let slot = self.next_temp;
+		self.next_temp += 1;
+		self.frame = self.frame.max(self.next_temp);
+		Arg::Relative(slot)
+	}
+	fn var(&self, name: &str, line: usize) -> Result<i64, Error> {
+		match self.scopes.iter().rev().find_map(|scope| scope.get(name)) {
+			Some(&slot) => Ok(slot),
+			None => Err(Error::new(line, format!("undefined variable `{}`", name))),
+		}
+	}
+
+	fn function(&mut self, function: &'a Function) -> Result<(), Error> {
+		let (label, _) = self.functions[function.name.as_str()];
+		self.bind(label);
+		// Frame layout: return address, parameters, locals, temporaries
+		let mut params = HashMap::new();
+		for (index, param) in function.params.iter().enumerate() {
+			if params.insert(param.as_str(), index as i64 + 1).is_some() {
+				return Err(Error::new(function.line, format!("duplicate parameter `{}`", param)));
+			}
+		}
+		self.scopes = vec![params];
+		self.loops.clear();
+		self.next_local = function.params.len() as i64 + 1;
+		self.temp_base = self.next_local + count_locals(&function.body) as i64;
+		self.frame = self.temp_base;
+		self.block(&function.body)?;
+		self.ret(None, function.line)?;
+
+		for (at, patch) in self.frame_patches.drain(..) {
+			self.code[at] = match patch {
+				Patch::Frame(offset) => self.frame + offset,
+				Patch::NegFrame => -self.frame,
+				Patch::Label(_) => unreachable!(),
+			};
+		}
+		Ok(())
+	}
+	fn block(&mut self, stmts: &'a [Stmt]) -> Result<(), Error> {
+		self.scopes.push(HashMap::new());
+		for stmt in stmts {
+			self.stmt(stmt)?;
+		}
+		self.scopes.pop();
+		Ok(())
+	}
+	fn stmt(&mut self, stmt: &'a Stmt) -> Result<(), Error> {
+		let line = stmt.line;
+		self.next_temp = self.temp_base;
+		match &stmt.kind {
+			StmtKind::Let(name, value) => {
+				let slot = self.next_local;
+				self.next_local += 1;
+				self.eval_into(value, Arg::Relative(slot), line)?;
+				self.scopes.last_mut().unwrap().insert(name, slot);
+			},
+			StmtKind::Assign(name, value) => {
+				let slot = self.var(name, line)?;
+				self.eval_into(value, Arg::Relative(slot), line)?;
+			},
+			StmtKind::If(cond, then, otherwise) => {
+				let (other, end) = (self.label(), self.label());
+				let cond = self.expr(cond, line)?;
+				self.jump_if_false(cond, other);
+				self.block(then)?;
+				if !otherwise.is_empty() {
+					self.jump(end);
+				}
+				self.bind(other);
+				self.block(otherwise)?;
+				self.bind(end);
+			},
+			StmtKind::While(cond, body) => {
+				let (start, end) = (self.label(), self.label());
+				self.bind(start);
+				let cond = self.expr(cond, line)?;
+				self.jump_if_false(cond, end);
+				self.loops.push((start, end));
+				self.block(body)?;
+				self.loops.pop();
+				self.jump(start);
+				self.bind(end);
+			},
+			StmtKind::Return(value) => self.ret(value.as_ref(), line)?,
+			StmtKind::Break | StmtKind::Continue => {
+				let &(start, end) = match self.loops.last() {
+					Some(labels) => labels,
+					None => return Err(Error::new(line, "`break` or `continue` outside of a loop".to_string())),
+				};
+				self.jump(if stmt.kind == StmtKind::Break { end } else { start });
+			},
+			StmtKind::Expr(expr) => {
+				self.expr(expr, line)?;
+			},
+		}
+		Ok(())
+	}
+	fn ret(&mut self, value: Option<&'a Expr>, line: usize) -> Result<(), Error> {
+		// The return value is passed in the first cell after the return address
+		match value {
+			Some(value) => self.eval_into(value, Arg::Relative(1), line)?,
+			None => self.mov(Arg::Immediate(0), Arg::Relative(1)),
+		}
+		self.emit(Instr::JumpIfFalse { arg: Arg::Immediate(0), target: Arg::Relative(0) }, &[]);
+		Ok(())
+	}
+
+	/// Evaluates an expression, returning the parameter holding its value.
+	fn expr(&mut self, expr: &'a Expr, line: usize) -> Result<Arg, Error> {
+		match expr {
+			&Expr::Number(value) => Ok(Arg::Immediate(value)),
+			Expr::Var(name) => self.var(name, line).map(Arg::Relative),
+			Expr::Call(name, args) => self.call(name, args, line),
+			_ => {
+				let temp = self.temp();
+				self.eval_into(expr, temp, line)?;
+				Ok(temp)
+			},
+		}
+	}
+	/// Evaluates an expression into `dest`.
+	///
+	/// The destination is only written after the operands are read, so it may be one of the operands.
+	fn eval_into(&mut self, expr: &'a Expr, dest: Arg, line: usize) -> Result<(), Error> {
+		match expr {
+			Expr::Unary(op, operand) => {
+				let src1 = self.expr(operand, line)?;
+				self.emit(match op {
+					UnOp::Neg => Instr::Mul { src1, src2: Arg::Immediate(-1), dest },
+					UnOp::Not => Instr::Equals { src1, src2: Arg::Immediate(0), dest },
+				}, &[]);
+			},
+			Expr::Binary(op @ BinOp::And, lhs, rhs) | Expr::Binary(op @ BinOp::Or, lhs, rhs) => {
+				// Short-circuit into a temporary so `dest` isn't written before `rhs` is read
+				let (temp, end) = (self.temp(), self.label());
+				let lhs = self.expr(lhs, line)?;
+				self.mov(Arg::Immediate((*op == BinOp::Or) as i64), temp);
+				self.emit(match op {
+					BinOp::And => Instr::JumpIfFalse { arg: lhs, target: Arg::Immediate(0) },
+					_ => Instr::JumpIfTrue { arg: lhs, target: Arg::Immediate(0) },
+				}, &[(1, Patch::Label(end))]);
+				let rhs = self.expr(rhs, line)?;
+				self.emit(Instr::Equals { src1: rhs, src2: Arg::Immediate(0), dest: temp }, &[]);
+				self.emit(Instr::Equals { src1: temp, src2: Arg::Immediate(0), dest: temp }, &[]);
+				self.bind(end);
+				self.mov(temp, dest);
+			},
+			Expr::Binary(op, lhs, rhs) => {
+				let src1 = self.expr(lhs, line)?;
+				let src2 = self.expr(rhs, line)?;
+				let negate = matches!(op, BinOp::Ne | BinOp::Le | BinOp::Ge);
+				match op {
+					BinOp::Add => self.emit(Instr::Add { src1, src2, dest }, &[]),
+					BinOp::Sub => match src2 {
+						Arg::Immediate(i64::MIN) => self.sub_min(src1, dest),
+						Arg::Immediate(value) => self.emit(Instr::Add { src1, src2: Arg::Immediate(-value), dest }, &[]),
+						_ => {
+							// Negating the minimum overflows, it's subtracted separately
+							let (temp, negate, end) = (self.temp(), self.label(), self.label());
+							self.emit(Instr::Equals { src1: src2, src2: Arg::Immediate(i64::MIN), dest: temp }, &[]);
+							self.jump_if_false(temp, negate);
+							self.sub_min(src1, dest);
+							self.jump(end);
+							self.bind(negate);
+							self.emit(Instr::Mul { src1: src2, src2: Arg::Immediate(-1), dest: temp }, &[]);
+							self.emit(Instr::Add { src1, src2: temp, dest }, &[]);
+							self.bind(end);
+						},
+					},
+					BinOp::Mul => self.emit(Instr::Mul { src1, src2, dest }, &[]),
+					BinOp::Eq | BinOp::Ne => self.emit(Instr::Equals { src1, src2, dest }, &[]),
+					BinOp::Lt | BinOp::Ge => self.emit(Instr::LessThan { src1, src2, dest }, &[]),
+					BinOp::Gt | BinOp::Le => self.emit(Instr::LessThan { src1: src2, src2: src1, dest }, &[]),
+					BinOp::And | BinOp::Or => unreachable!(),
+				}
+				if negate {
+					self.emit(Instr::Equals { src1: dest, src2: Arg::Immediate(0), dest }, &[]);
+				}
+			},
+			_ => {
+				let src = self.expr(expr, line)?;
+				self.mov(src, dest);
+			},
+		}
+		Ok(())
+	}
+	/// Subtracts `i64::MIN` as adding `i64::MAX` and one, which only overflows when the difference does.
+	fn sub_min(&mut self, src: Arg, dest: Arg) {
+		self.emit(Instr::Add { src1: src, src2: Arg::Immediate(i64::MAX), dest }, &[]);
+		self.emit(Instr::Add { src1: dest, src2: Arg::Immediate(1), dest }, &[]);
+	}
+	fn call(&mut self, name: &'a str, args: &'a [Expr], line: usize) -> Result<Arg, Error> {
+		let (label, arity) = match (self.functions.get(name), BUILTINS.iter().find(|&&(builtin, _)| builtin == name)) {
+			(Some(&(label, arity)), _) => (Some(label), arity),
+			(None, Some(&(_, arity))) => (None, arity),
+			(None, None) => return Err(Error::new(line, format!("undefined function `{}`", name))),
+		};
+		if args.len() != arity {
+			return Err(Error::new(line, format!("`{}` takes {} arguments but {} were given", name, arity, args.len())));
+		}
+		let values = args.iter().map(|arg| self.expr(arg, line)).collect::<Result<Vec<_>, _>>()?;
+		let label = match label {
+			Some(label) => label,
+			None if name == "input" => {
+				let temp = self.temp();
+				self.emit(Instr::Input { dest: temp }, &[]);
+				return Ok(temp);
+			},
+			None => {
+				self.emit(Instr::Output { src: values[0] }, &[]);
+				return Ok(Arg::Immediate(0));
+			},
+		};
+
+		// The callee's frame starts right after the caller's frame
+		for (index, &value) in values.iter().enumerate() {
+			self.emit(Instr::Add { src1: value, src2: Arg::Immediate(0), dest: Arg::Relative(0) }, &[(2, Patch::Frame(index as i64 + 1))]);
+		}
+		let ret = self.label();
+		self.emit(Instr::Add { src1: Arg::Immediate(0), src2: Arg::Immediate(0), dest: Arg::Relative(0) }, &[(0, Patch::Label(ret)), (2, Patch::Frame(0))]);
+		self.emit(Instr::AdjustRelBase { value: Arg::Immediate(0) }, &[(0, Patch::Frame(0))]);
+		self.jump(label);
+		self.bind(ret);
+		self.emit(Instr::AdjustRelBase { value: Arg::Immediate(0) }, &[(0, Patch::NegFrame)]);
+		let temp = self.temp();
+		self.emit(Instr::Add { src1: Arg::Relative(0), src2: Arg::Immediate(0), dest: temp }, &[(0, Patch::Frame(1))]);
+		Ok(temp)
+	}
+}
+
+fn count_locals(stmts: &[Stmt]) -> usize {
+	stmts.iter().map(|stmt| match &stmt.kind {
+		StmtKind::Let(..) => 1,
+		StmtKind::If(_, then, otherwise) => count_locals(then) + count_locals(otherwise),
+		StmtKind::While(_, body) => count_locals(body),
+		_ => 0,
+	}).sum()
+}
+
+/// Generates the program image.
+pub fn generate(functions: &[Function]) -> Result<Vec<i64>, Error> {
+	let mut gen = Codegen::default();
+	for function in functions {
+		let name = function.name.as_str();
+		if BUILTINS.iter().any(|&(builtin, _)| builtin == name) {
+			return Err(Error::new(function.line, format!("cannot redefine builtin `{}`", name)));
+		}
+		let label = gen.label();
+		if gen.functions.insert(name, (label, function.params.len())).is_some() {
+			return Err(Error::new(function.line, format!("duplicate function `{}`", name)));
+		}
+	}
+	let main = match functions.iter().find(|function| function.name == "main") {
+		Some(function) if !function.params.is_empty() => return Err(Error::new(function.line, "`main` must not take parameters".to_string())),
+		Some(_) => gen.functions["main"].0,
+		None => return Err(Error::new(1, "missing function `main`".to_string())),
+	};
+
+	// Startup: place the stack after the code and call main with the return address pointing at a halt
+	let (stack, halt) = (gen.label(), gen.label());
+	gen.emit(Instr::AdjustRelBase { value: Arg::Immediate(0) }, &[(0, Patch::Label(stack))]);
+	gen.emit(Instr::Add { src1: Arg::Immediate(0), src2: Arg::Immediate(0), dest: Arg::Relative(0) }, &[(0, Patch::Label(halt))]);
+	gen.jump(main);
+	gen.bind(halt);
+	gen.emit(Instr::Halt, &[]);
+
+	for function in functions {
+		gen.function(function)?;
+	}
+	gen.bind(stack);
+	for &(at, label) in &gen.label_patches {
+		gen.code[at] = gen.labels[label].unwrap() as i64;
+	}
+	Ok(gen.code)
+}
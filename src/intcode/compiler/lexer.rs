@@ -0,0 +1,80 @@
+use super::Error;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Token {
+	Ident(String),
+	Number(i64),
+	Fn,
+	Let,
+	If,
+	Else,
+	While,
+	Return,
+	Break,
+	Continue,
+	/// Punctuation and operators.
+	Punct(&'static str),
+}
+
+const PUNCTS: &[&str] = &["==", "!=", "<=", ">=", "&&", "||", "(", ")", "{", "}", ",", ";", "=", "+", "-", "*", "<", ">", "!"];
+
+/// Splits the source into tokens with their line numbers.
+pub fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, Error> {
+	let mut tokens = Vec::new();
+	for (index, line) in source.lines().enumerate() {
+		let line_nr = index + 1;
+		let line = match line.find("//") {
+			Some(comment) => &line[..comment],
+			None => line,
+		};
+		let mut rest = line.trim_start();
+		while !rest.is_empty() {
+			let first = rest.as_bytes()[0];
+			let len = if first.is_ascii_digit() {
+				let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+				// `9223372036854775808` wraps to `i64::MIN`, the parser only accepts it after a minus sign
+				let number = rest[..len].parse::<u64>().ok().filter(|&number| number <= 1 << 63)
+					.ok_or_else(|| Error::new(line_nr, format!("number too large: {}", &rest[..len])))? as i64;
+				tokens.push((Token::Number(number), line_nr));
+				len
+			}
+			else if first.is_ascii_alphabetic() || first == b'_' {
+				let len = rest.bytes().take_while(|&c| c.is_ascii_alphanumeric() || c == b'_').count();
+				let token = match &rest[..len] {
+					"fn" => Token::Fn,
+					"let" => Token::Let,
+					"if" => Token::If,
+					"else" => Token::Else,
+					"while" => Token::While,
+					"return" => Token::Return,
+					"break" => Token::Break,
+					"continue" => Token::Continue,
+					ident => Token::Ident(ident.to_string()),
+				};
+				tokens.push((token, line_nr));
+				len
+			}
+			else {
+				match PUNCTS.iter().find(|&&punct| rest.starts_with(punct)) {
+					Some(&punct) => {
+						tokens.push((Token::Punct(punct), line_nr));
+						punct.len()
+					},
+					None => return Err(Error::new(line_nr, format!("unexpected character: {:?}", rest.chars().next().unwrap()))),
+				}
+			};
+			rest = rest[len..].trim_start();
+		}
+	}
+	Ok(tokens)
+}
+
+#[test]
+fn test_tokenize() {
+	let tokens = tokenize("let x1 = 12 <= y; // comment\nfn").unwrap();
+	let tokens = tokens.into_iter().map(|(token, _)| token).collect::<Vec<_>>();
+	assert_eq!(tokens, [
+		Token::Let, Token::Ident("x1".into()), Token::Punct("="), Token::Number(12),
+		Token::Punct("<="), Token::Ident("y".into()), Token::Punct(";"), Token::Fn,
+	]);
+}
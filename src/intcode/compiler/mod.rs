@@ -0,0 +1,173 @@
+/*!
+Compiler from a small C-like language to Intcode.
+
+A program is a list of functions, execution starts at `main`:
+
+```text
+fn main() {
+    let n = input();
+    let i = 0;
+    while i < n {
+        output(fib(i));
+        i = i + 1;
+    }
+}
+fn fib(n) {
+    if n < 2 { return n; }
+    return fib(n - 1) + fib(n - 2);
+}
+```
+
+All values are integers. The language has
+* `let` declarations, assignments, `if`/`else`, `while` with `break` and `continue` and `return`,
+* the operators `+ - *`, comparisons `== != < <= > >=`, short-circuiting `&& ||` and `!`, which evaluate to `0` or `1`,
+* functions with any number of parameters which return `0` unless they return a value,
+* the builtins `input()` and `output(value)`.
+
+The generated code needs relative mode and runs on the day 9 machine.
+Function frames live on a stack after the code, addressed with relative mode: a call moves the relative base past the caller's frame.
+*/
+
+use std::fmt;
+
+mod codegen;
+mod lexer;
+mod parser;
+
+/// A compile error with the line it occurred on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+	pub line: usize,
+	pub message: String,
+}
+impl Error {
+	fn new(line: usize, message: String) -> Error {
+		Error { line, message }
+	}
+}
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "line {}: {}", self.line, self.message)
+	}
+}
+
+/// Compiles the source to an Intcode program.
+pub fn compile(source: &str) -> Result<Vec<i64>, Error> {
+	let tokens = lexer::tokenize(source)?;
+	let functions = parser::parse(tokens)?;
+	codegen::generate(&functions)
+}
+
+#[cfg(test)]
+fn run(source: &str, input: &[i64]) -> Vec<i64> {
+	let program = compile(source).unwrap_or_else(|err| panic!("{}", err));
+	super::execute(&program, input).unwrap()
+}
+
+#[test]
+fn test_fib() {
+	let source = "
+		fn main() {
+			let n = input();
+			let i = 0;
+			while i < n {
+				output(fib(i));
+				i = i + 1;
+			}
+		}
+		fn fib(n) {
+			if n < 2 { return n; }
+			return fib(n - 1) + fib(n - 2);
+		}";
+	assert_eq!(run(source, &[10]), [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+}
+
+#[test]
+fn test_operators() {
+	let source = "
+		fn main() {
+			let a = input();
+			let b = input();
+			output(a + b); output(a - b); output(a * b); output(-a);
+			output(a == b); output(a != b); output(a < b); output(a <= b); output(a > b); output(a >= b);
+			output(a && b); output(a || b); output(!a);
+			output(2 + 3 * 4 - (1 - 5));
+		}";
+	assert_eq!(run(source, &[3, 5]), [8, -2, 15, -3, 0, 1, 1, 1, 0, 0, 1, 1, 0, 18]);
+	assert_eq!(run(source, &[5, 5]), [10, 0, 25, -5, 1, 0, 0, 1, 0, 1, 1, 1, 0, 18]);
+	assert_eq!(run(source, &[0, 7]), [7, -7, 0, 0, 0, 1, 1, 1, 0, 0, 0, 1, 1, 18]);
+}
+
+#[test]
+fn test_control_flow() {
+	let source = "
+		// Short-circuiting skips the call
+		fn loud(x) { output(x); return x; }
+		fn classify(x) {
+			if x < 0 { return -1; } else if x == 0 { return 0; } else { return 1; }
+		}
+		fn main() {
+			let x = 0 && loud(1);
+			let y = 1 || loud(2);
+			output(x + y);
+			let i = 0;
+			let sum = 0;
+			while 1 {
+				i = i + 1;
+				if i > 10 { break; }
+				if i * 2 == i + i && i == 3 { continue; }
+				let i = i * 100;
+				sum = sum + i;
+			}
+			output(sum);
+			output(classify(-5)); output(classify(0)); output(classify(9));
+		}";
+	assert_eq!(run(source, &[]), [1, 5200, -1, 0, 1]);
+}
+
+#[test]
+fn test_calls() {
+	let source = "
+		fn add3(a, b, c) { return a + b + c; }
+		fn nothing() { }
+		fn main() {
+			output(add3(add3(1, 2, 3), add3(4, 5, 6), 7 * add3(1, 1, 1)));
+			output(nothing());
+			let x = 4;
+			x = x * x - x;
+			output(x);
+		}";
+	assert_eq!(run(source, &[]), [42, 0, 12]);
+}
+
+#[test]
+fn test_errors() {
+	let error = |source| compile(source).unwrap_err();
+	assert_eq!(error("fn main() { x = 1; }"), Error::new(1, "undefined variable `x`".into()));
+	assert_eq!(error("fn main() {\n f(); }"), Error::new(2, "undefined function `f`".into()));
+	assert_eq!(error("fn f(a) {} fn main() { f(); }"), Error::new(1, "`f` takes 1 arguments but 0 were given".into()));
+	assert_eq!(error("fn main() {\n\n break; }"), Error::new(3, "`break` or `continue` outside of a loop".into()));
+	assert_eq!(error("fn f() {}"), Error::new(1, "missing function `main`".into()));
+	assert_eq!(error("fn main() { let x = ; }"), Error::new(1, "expected expression, found Punct(\";\")".into()));
+	assert_eq!(error("fn main() { output(1) }"), Error::new(1, "expected `;`, found Punct(\"}\")".into()));
+	assert_eq!(error("fn main() { 1 $ 2; }"), Error::new(1, "unexpected character: '$'".into()));
+	assert_eq!(error("fn f() {}\n\nfn main(x) {}"), Error::new(3, "`main` must not take parameters".into()));
+	assert_eq!(error("fn main() {\n output(9223372036854775808); }"), Error::new(2, "number too large: 9223372036854775808".into()));
+	assert_eq!(error("fn main() { output(-9223372036854775809); }"), Error::new(1, "number too large: 9223372036854775809".into()));
+}
+
+#[test]
+fn test_limits() {
+	let source = "fn main() { output(-9223372036854775808); output(9223372036854775807); output(- -5); }";
+	assert_eq!(run(source, &[]), [i64::MIN, i64::MAX, 5]);
+	let source = "
+		fn main() {
+			let a = input();
+			let b = input();
+			output(a - b);
+			output(a - -9223372036854775808);
+			output(-1 - b);
+		}";
+	assert_eq!(run(source, &[-1, i64::MIN]), [i64::MAX, i64::MAX, i64::MAX]);
+	assert_eq!(run(source, &[-8, 3]), [-11, 9223372036854775800, -4]);
+}
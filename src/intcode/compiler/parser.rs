@@ -0,0 +1,293 @@
+use super::Error;
+use super::lexer::Token;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BinOp {
+	Add,
+	Sub,
+	Mul,
+	Eq,
+	Ne,
+	Lt,
+	Le,
+	Gt,
+	Ge,
+	And,
+	Or,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum UnOp {
+	Neg,
+	Not,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Expr {
+	Number(i64),
+	Var(String),
+	Call(String, Vec<Expr>),
+	Unary(UnOp, Box<Expr>),
+	Binary(BinOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StmtKind {
+	Let(String, Expr),
+	Assign(String, Expr),
+	If(Expr, Vec<Stmt>, Vec<Stmt>),
+	While(Expr, Vec<Stmt>),
+	Return(Option<Expr>),
+	Break,
+	Continue,
+	Expr(Expr),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stmt {
+	pub line: usize,
+	pub kind: StmtKind,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Function {
+	pub line: usize,
+	pub name: String,
+	pub params: Vec<String>,
+	pub body: Vec<Stmt>,
+}
+
+/// Binary operators from the lowest to the highest precedence.
+const LEVELS: &[&[(&str, BinOp)]] = &[
+	&[("||", BinOp::Or)],
+	&[("&&", BinOp::And)],
+	&[("==", BinOp::Eq), ("!=", BinOp::Ne), ("<", BinOp::Lt), ("<=", BinOp::Le), (">", BinOp::Gt), (">=", BinOp::Ge)],
+	&[("+", BinOp::Add), ("-", BinOp::Sub)],
+	&[("*", BinOp::Mul)],
+];
+
+struct Parser {
+	tokens: Vec<(Token, usize)>,
+	pos: usize,
+}
+impl Parser {
+	fn peek(&self) -> Option<&Token> {
+		self.tokens.get(self.pos).map(|(token, _)| token)
+	}
+	fn line(&self) -> usize {
+		match self.tokens.get(self.pos) {
+			Some(&(_, line)) => line,
+			None => self.tokens.last().map(|&(_, line)| line).unwrap_or(1),
+		}
+	}
+	fn error<T>(&self, expected: &str) -> Result<T, Error> {
+		let found = match self.peek() {
+			Some(token) => format!("{:?}", token),
+			None => "end of input".to_string(),
+		};
+		Err(Error::new(self.line(), format!("expected {}, found {}", expected, found)))
+	}
+	fn eat(&mut self, token: &Token) -> bool {
+		let found = self.peek() == Some(token);
+		if found {
+			self.pos += 1;
+		}
+		found
+	}
+	fn expect(&mut self, punct: &'static str) -> Result<(), Error> {
+		if self.eat(&Token::Punct(punct)) { Ok(()) } else { self.error(&format!("`{}`", punct)) }
+	}
+	fn ident(&mut self) -> Result<String, Error> {
+		match self.peek() {
+			Some(Token::Ident(name)) => {
+				let name = name.clone();
+				self.pos += 1;
+				Ok(name)
+			},
+			_ => self.error("identifier"),
+		}
+	}
+
+	fn function(&mut self) -> Result<Function, Error> {
+		let line = self.line();
+		if !self.eat(&Token::Fn) {
+			return self.error("`fn`");
+		}
+		let name = self.ident()?;
+		self.expect("(")?;
+		let mut params = Vec::new();
+		if !self.eat(&Token::Punct(")")) {
+			loop {
+				params.push(self.ident()?);
+				if self.eat(&Token::Punct(")")) {
+					break;
+				}
+				self.expect(",")?;
+			}
+		}
+		let body = self.block()?;
+		Ok(Function { line, name, params, body })
+	}
+	fn block(&mut self) -> Result<Vec<Stmt>, Error> {
+		self.expect("{")?;
+		let mut stmts = Vec::new();
+		while !self.eat(&Token::Punct("}")) {
+			if self.peek().is_none() {
+				return self.error("`}`");
+			}
+			stmts.push(self.stmt()?);
+		}
+		Ok(stmts)
+	}
+	fn stmt(&mut self) -> Result<Stmt, Error> {
+		let line = self.line();
+		let kind = match self.peek() {
+			Some(Token::Let) => {
+				self.pos += 1;
+				let name = self.ident()?;
+				self.expect("=")?;
+				let value = self.expr(0)?;
+				self.expect(";")?;
+				StmtKind::Let(name, value)
+			},
+			Some(Token::If) => {
+				self.pos += 1;
+				let cond = self.expr(0)?;
+				let then = self.block()?;
+				let otherwise = if !self.eat(&Token::Else) {
+					Vec::new()
+				}
+				else if self.peek() == Some(&Token::If) {
+					vec![self.stmt()?]
+				}
+				else {
+					self.block()?
+				};
+				StmtKind::If(cond, then, otherwise)
+			},
+			Some(Token::While) => {
+				self.pos += 1;
+				let cond = self.expr(0)?;
+				StmtKind::While(cond, self.block()?)
+			},
+			Some(Token::Return) => {
+				self.pos += 1;
+				let value = if self.peek() == Some(&Token::Punct(";")) { None } else { Some(self.expr(0)?) };
+				self.expect(";")?;
+				StmtKind::Return(value)
+			},
+			Some(Token::Break) => {
+				self.pos += 1;
+				self.expect(";")?;
+				StmtKind::Break
+			},
+			Some(Token::Continue) => {
+				self.pos += 1;
+				self.expect(";")?;
+				StmtKind::Continue
+			},
+			Some(Token::Ident(_)) if self.tokens.get(self.pos + 1).map(|(token, _)| token) == Some(&Token::Punct("=")) => {
+				let name = self.ident()?;
+				self.pos += 1;
+				let value = self.expr(0)?;
+				self.expect(";")?;
+				StmtKind::Assign(name, value)
+			},
+			_ => {
+				let expr = self.expr(0)?;
+				self.expect(";")?;
+				StmtKind::Expr(expr)
+			},
+		};
+		Ok(Stmt { line, kind })
+	}
+	fn expr(&mut self, level: usize) -> Result<Expr, Error> {
+		if level == LEVELS.len() {
+			return self.unary();
+		}
+		let mut lhs = self.expr(level + 1)?;
+		'outer: loop {
+			for &(punct, op) in LEVELS[level] {
+				if self.eat(&Token::Punct(punct)) {
+					let rhs = self.expr(level + 1)?;
+					lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+					continue 'outer;
+				}
+			}
+			return Ok(lhs);
+		}
+	}
+	fn unary(&mut self) -> Result<Expr, Error> {
+		if self.eat(&Token::Punct("-")) {
+			// Fold negative literals so the full i64 range can be written
+			if self.eat(&Token::Number(i64::MIN)) {
+				return Ok(Expr::Number(i64::MIN));
+			}
+			return Ok(match self.unary()? {
+				Expr::Number(value) if value != i64::MIN => Expr::Number(-value),
+				expr => Expr::Unary(UnOp::Neg, Box::new(expr)),
+			});
+		}
+		if self.eat(&Token::Punct("!")) {
+			return Ok(Expr::Unary(UnOp::Not, Box::new(self.unary()?)));
+		}
+		self.primary()
+	}
+	fn primary(&mut self) -> Result<Expr, Error> {
+		match self.peek() {
+			Some(&Token::Number(i64::MIN)) => Err(Error::new(self.line(), "number too large: 9223372036854775808".to_string())),
+			Some(&Token::Number(value)) => {
+				self.pos += 1;
+				Ok(Expr::Number(value))
+			},
+			Some(Token::Ident(_)) => {
+				let name = self.ident()?;
+				if !self.eat(&Token::Punct("(")) {
+					return Ok(Expr::Var(name));
+				}
+				let mut args = Vec::new();
+				if !self.eat(&Token::Punct(")")) {
+					loop {
+						args.push(self.expr(0)?);
+						if self.eat(&Token::Punct(")")) {
+							break;
+						}
+						self.expect(",")?;
+					}
+				}
+				Ok(Expr::Call(name, args))
+			},
+			Some(Token::Punct("(")) => {
+				self.pos += 1;
+				let expr = self.expr(0)?;
+				self.expect(")")?;
+				Ok(expr)
+			},
+			_ => self.error("expression"),
+		}
+	}
+}
+
+/// Parses a program: a sequence of function definitions.
+pub fn parse(tokens: Vec<(Token, usize)>) -> Result<Vec<Function>, Error> {
+	let mut parser = Parser { tokens, pos: 0 };
+	let mut functions = Vec::new();
+	while parser.peek().is_some() {
+		functions.push(parser.function()?);
+	}
+	Ok(functions)
+}
+
+#[test]
+fn test_precedence() {
+	let tokens = super::lexer::tokenize("fn f() { return 1 + 2 * -x < 3 || !y; }").unwrap();
+	let functions = parse(tokens).unwrap();
+	let num = |value| Box::new(Expr::Number(value));
+	let var = |name: &str| Box::new(Expr::Var(name.to_string()));
+	let expected = Expr::Binary(BinOp::Or,
+		Box::new(Expr::Binary(BinOp::Lt,
+			Box::new(Expr::Binary(BinOp::Add, num(1), Box::new(Expr::Binary(BinOp::Mul, num(2), Box::new(Expr::Unary(UnOp::Neg, var("x"))))))),
+			num(3))),
+		Box::new(Expr::Unary(UnOp::Not, var("y"))));
+	assert_eq!(functions[0].body[0].kind, StmtKind::Return(Some(expected)));
+}
@@ -0,0 +1,124 @@
+/*!
+Intcode conformance suite.
+
+Every example program from the puzzle descriptions of days 2, 5 and 9, plus a few cases for relative mode writes which the examples don't cover.
+Each case checks the complete output and the final memory.
+
+Validate a backend with `conformance::check(&backend)` or run the suite on all built-in backends with `cargo run --bin conformance`.
+*/
+
+use std::fmt;
+use super::backend::{Backend, Level, Outcome, Status};
+
+/// Instruction budget for every case.
+pub const BUDGET: u64 = 10000;
+
+#[derive(Copy, Clone, Debug)]
+pub struct Case {
+	pub name: &'static str,
+	/// Minimum level required to run the program.
+	pub level: Level,
+	pub program: &'static [i64],
+	pub input: &'static [i64],
+	pub output: &'static [i64],
+	/// Final memory as `(address, value)` changes to the program.
+	pub memory: &'static [(usize, i64)],
+}
+impl Case {
+	/// Returns the expected outcome.
+	pub fn expected(&self) -> Outcome {
+		let mut memory = self.program.to_vec();
+		for &(address, value) in self.memory {
+			if address >= memory.len() {
+				memory.resize(address + 1, 0);
+			}
+			memory[address] = value;
+		}
+		while memory.last() == Some(&0) {
+			memory.pop();
+		}
+		Outcome { status: Status::Halted, output: self.output.to_vec(), memory }
+	}
+}
+
+const LARGE: &[i64] = &[3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99];
+
+pub const CASES: &[Case] = &[
+	// Day 2
+	Case { name: "day2 example", level: Level::Day2, program: &[1,9,10,3,2,3,11,0,99,30,40,50], input: &[], output: &[], memory: &[(0, 3500), (3, 70)] },
+	Case { name: "day2 add", level: Level::Day2, program: &[1,0,0,0,99], input: &[], output: &[], memory: &[(0, 2)] },
+	Case { name: "day2 mul", level: Level::Day2, program: &[2,3,0,3,99], input: &[], output: &[], memory: &[(3, 6)] },
+	Case { name: "day2 mul after halt", level: Level::Day2, program: &[2,4,4,5,99,0], input: &[], output: &[], memory: &[(5, 9801)] },
+	Case { name: "day2 self-modify", level: Level::Day2, program: &[1,1,1,4,99,5,6,0,99], input: &[], output: &[], memory: &[(0, 30), (4, 2)] },
+	// Day 5
+	Case { name: "day5 echo", level: Level::Day5, program: &[3,0,4,0,99], input: &[42], output: &[42], memory: &[(0, 42)] },
+	Case { name: "day5 immediate mul", level: Level::Day5, program: &[1002,4,3,4,33], input: &[], output: &[], memory: &[(4, 99)] },
+	Case { name: "day5 negative", level: Level::Day5, program: &[1101,100,-1,4,0], input: &[], output: &[], memory: &[(4, 99)] },
+	Case { name: "day5 equal position", level: Level::Day5, program: &[3,9,8,9,10,9,4,9,99,-1,8], input: &[8], output: &[1], memory: &[(9, 1)] },
+	Case { name: "day5 not equal position", level: Level::Day5, program: &[3,9,8,9,10,9,4,9,99,-1,8], input: &[7], output: &[0], memory: &[(9, 0)] },
+	Case { name: "day5 less position", level: Level::Day5, program: &[3,9,7,9,10,9,4,9,99,-1,8], input: &[5], output: &[1], memory: &[(9, 1)] },
+	Case { name: "day5 not less position", level: Level::Day5, program: &[3,9,7,9,10,9,4,9,99,-1,8], input: &[8], output: &[0], memory: &[(9, 0)] },
+	Case { name: "day5 equal immediate", level: Level::Day5, program: &[3,3,1108,-1,8,3,4,3,99], input: &[8], output: &[1], memory: &[(3, 1)] },
+	Case { name: "day5 not equal immediate", level: Level::Day5, program: &[3,3,1108,-1,8,3,4,3,99], input: &[9], output: &[0], memory: &[(3, 0)] },
+	Case { name: "day5 less immediate", level: Level::Day5, program: &[3,3,1107,-1,8,3,4,3,99], input: &[-3], output: &[1], memory: &[(3, 1)] },
+	Case { name: "day5 not less immediate", level: Level::Day5, program: &[3,3,1107,-1,8,3,4,3,99], input: &[10], output: &[0], memory: &[(3, 0)] },
+	Case { name: "day5 jump position zero", level: Level::Day5, program: &[3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9], input: &[0], output: &[0], memory: &[(12, 0)] },
+	Case { name: "day5 jump position nonzero", level: Level::Day5, program: &[3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9], input: &[5], output: &[1], memory: &[(12, 5), (13, 1)] },
+	Case { name: "day5 jump immediate zero", level: Level::Day5, program: &[3,3,1105,-1,9,1101,0,0,12,4,12,99,1], input: &[0], output: &[0], memory: &[(3, 0), (12, 0)] },
+	Case { name: "day5 jump immediate nonzero", level: Level::Day5, program: &[3,3,1105,-1,9,1101,0,0,12,4,12,99,1], input: &[5], output: &[1], memory: &[(3, 5)] },
+	Case { name: "day5 below eight", level: Level::Day5, program: LARGE, input: &[7], output: &[999], memory: &[(21, 7)] },
+	Case { name: "day5 eight", level: Level::Day5, program: LARGE, input: &[8], output: &[1000], memory: &[(20, 1000), (21, 8)] },
+	Case { name: "day5 above eight", level: Level::Day5, program: LARGE, input: &[9], output: &[1001], memory: &[(20, 1001), (21, 9)] },
+	// Day 9
+	Case { name: "day9 quine", level: Level::Day9, program: &[109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99], input: &[], output: &[109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99], memory: &[(100, 16), (101, 1)] },
+	Case { name: "day9 large multiply", level: Level::Day9, program: &[1102,34915192,34915192,7,4,7,99,0], input: &[], output: &[1219070632396864], memory: &[(7, 1219070632396864)] },
+	Case { name: "day9 large output", level: Level::Day9, program: &[104,1125899906842624,99], input: &[], output: &[1125899906842624], memory: &[] },
+	Case { name: "day9 relative base", level: Level::Day9, program: &[109,2000,109,19,204,-34,99], input: &[], output: &[0], memory: &[] },
+	// Relative mode writes
+	Case { name: "relative input", level: Level::Day9, program: &[109,7,203,0,204,0,99], input: &[42], output: &[42], memory: &[(7, 42)] },
+	Case { name: "relative add", level: Level::Day9, program: &[109,10,21101,2,3,0,204,0,99], input: &[], output: &[5], memory: &[(10, 5)] },
+	Case { name: "relative compare", level: Level::Day9, program: &[109,-1,21107,3,4,10,204,10,99], input: &[], output: &[1], memory: &[(9, 1)] },
+];
+
+/// A case where the backend's outcome differs from the expected one.
+#[derive(Clone, Debug)]
+pub struct Failure {
+	pub case: &'static str,
+	pub expected: Outcome,
+	pub actual: Outcome,
+}
+impl fmt::Display for Failure {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "Case `{}`:", self.case)?;
+		writeln!(f, "  expected: {:?}, output: `{:?}`, memory: `{:?}`", self.expected.status, self.expected.output, self.expected.memory)?;
+		writeln!(f, "  actual:   {:?}, output: `{:?}`, memory: `{:?}`", self.actual.status, self.actual.output, self.actual.memory)
+	}
+}
+
+/// Runs every case supported by the backend.
+///
+/// Returns the number of cases run or the failures.
+pub fn check(backend: &dyn Backend) -> Result<usize, Vec<Failure>> {
+	let level = backend.features().level;
+	let mut count = 0;
+	let mut failures = Vec::new();
+	for case in CASES.iter().filter(|case| case.level <= level) {
+		let expected = case.expected();
+		let actual = backend.run(case.program, case.input, BUDGET);
+		if actual != expected {
+			failures.push(Failure { case: case.name, expected, actual });
+		}
+		count += 1;
+	}
+	if failures.is_empty() { Ok(count) } else { Err(failures) }
+}
+
+#[test]
+fn test_backends() {
+	for backend in &super::backend::backends() {
+		if let Err(failures) = check(&**backend) {
+			let failures = failures.iter().map(|failure| failure.to_string()).collect::<String>();
+			panic!("{} fails:\n{}", backend.name(), failures);
+		}
+	}
+}
@@ -0,0 +1,131 @@
+/*!
+Custom Intcode instructions.
+
+Opcodes not known to the machine are handed to an [`Extension`](trait.Extension.html) when running with [`step_with`](../struct.Intcode.html#method.step_with) or [`run_with`](../struct.Intcode.html#method.run_with).
+The parameters of a custom instruction are decoded like the built-in ones: the opcode's hundreds digit is the mode of the first parameter, and so on.
+
+The [`Registry`](struct.Registry.html) registers closures for opcodes:
+
+```
+use adventofcode2019::intcode::{Intcode, Registry, State};
+
+let mut trace = Vec::new();
+let mut registry = Registry::new();
+// Debug print the value of its parameter
+registry.register(50, 1, |vm, args| {
+    trace.push(vm.read(args[0])?);
+    Ok(State::Running)
+});
+
+let mut vm = Intcode::new(&[150,42,99]);
+assert_eq!(vm.run_with(&mut registry), Ok(State::Halted));
+drop(registry);
+assert_eq!(trace, [42]);
+```
+*/
+
+use super::{Arg, Fault, Intcode, State};
+
+pub trait Extension {
+	/// Returns the number of parameters of the custom instruction or `None` if the opcode isn't handled.
+	///
+	/// The `opcode` is without its parameter modes.
+	fn arity(&self, opcode: i64) -> Option<usize>;
+
+	/// Executes the custom instruction with its decoded parameters.
+	///
+	/// The instruction pointer is advanced past the instruction unless it was changed by the instruction,
+	/// or the instruction returns `State::Input` or `State::Halted` to leave it at the current instruction.
+	fn execute(&mut self, vm: &mut Intcode, opcode: i64, args: &[Arg]) -> Result<State, Fault>;
+}
+
+/// No custom instructions.
+impl Extension for () {
+	fn arity(&self, _opcode: i64) -> Option<usize> {
+		None
+	}
+	fn execute(&mut self, vm: &mut Intcode, opcode: i64, _args: &[Arg]) -> Result<State, Fault> {
+		Err(Fault::InvalidOpcode { ip: vm.ip, opcode })
+	}
+}
+
+type Hook<'a> = Box<dyn FnMut(&mut Intcode, &[Arg]) -> Result<State, Fault> + 'a>;
+
+struct Custom<'a> {
+	opcode: i64,
+	arity: usize,
+	hook: Hook<'a>,
+}
+
+/// Custom instructions implemented by closures.
+#[derive(Default)]
+pub struct Registry<'a> {
+	instrs: Vec<Custom<'a>>,
+}
+impl<'a> Registry<'a> {
+	pub fn new() -> Registry<'a> {
+		Registry::default()
+	}
+	/// Registers a custom instruction.
+	///
+	/// Custom opcodes are in the range `10..99` which is free of built-in instructions.
+	/// Panics if the opcode is outside this range or already registered.
+	pub fn register<F>(&mut self, opcode: i64, arity: usize, hook: F) -> &mut Registry<'a>
+		where F: FnMut(&mut Intcode, &[Arg]) -> Result<State, Fault> + 'a
+	{
+		if !(10..99).contains(&opcode) {
+			panic!("invalid custom opcode: {}", opcode);
+		}
+		if self.arity(opcode).is_some() {
+			panic!("custom opcode already registered: {}", opcode);
+		}
+		self.instrs.push(Custom { opcode, arity, hook: Box::new(hook) });
+		self
+	}
+}
+impl<'a> Extension for Registry<'a> {
+	fn arity(&self, opcode: i64) -> Option<usize> {
+		self.instrs.iter().find(|instr| instr.opcode == opcode).map(|instr| instr.arity)
+	}
+	fn execute(&mut self, vm: &mut Intcode, opcode: i64, args: &[Arg]) -> Result<State, Fault> {
+		match self.instrs.iter_mut().find(|instr| instr.opcode == opcode) {
+			Some(instr) => (instr.hook)(vm, args),
+			None => Err(Fault::InvalidOpcode { ip: vm.ip, opcode }),
+		}
+	}
+}
+
+#[test]
+fn test_host_call() {
+	let mut registry = Registry::new();
+	// Host call computing the maximum of two values
+	registry.register(20, 3, |vm, args| {
+		let value = vm.read(args[0])?.max(vm.read(args[1])?);
+		vm.write(args[2], value)?;
+		Ok(State::Running)
+	});
+	// Jump to the target and output it
+	registry.register(21, 1, |vm, args| {
+		let target = vm.read(args[0])?;
+		vm.ip = target as usize;
+		Ok(State::Output(target))
+	});
+
+	let mut vm = Intcode::new(&[21120,3,7,9,121,10,99,2,0,0,204,-7,99]);
+	assert_eq!(vm.run_with(&mut registry), Ok(State::Output(10)));
+	assert_eq!(vm.mem[9], 7);
+	vm.rel_base = 16;
+	assert_eq!(vm.run_with(&mut registry), Ok(State::Output(7)));
+	assert_eq!(vm.run_with(&mut registry), Ok(State::Halted));
+	assert_eq!(vm.steps, 3);
+}
+
+#[test]
+fn test_unregistered() {
+	let mut registry = Registry::new();
+	registry.register(20, 0, |_, _| Ok(State::Halted));
+	assert_eq!(Intcode::new(&[20]).run_with(&mut registry), Ok(State::Halted));
+	assert_eq!(Intcode::new(&[21]).run_with(&mut registry), Err(Fault::InvalidOpcode { ip: 0, opcode: 21 }));
+	assert_eq!(Intcode::new(&[20]).run(), Err(Fault::InvalidOpcode { ip: 0, opcode: 20 }));
+	assert_eq!(Intcode::new(&[320]).run_with(&mut registry), Ok(State::Halted));
+}
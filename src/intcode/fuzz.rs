@@ -0,0 +1,315 @@
+/*!
+Differential fuzzing of the Intcode backends.
+
+Generates random well-formed programs with input, runs them on every backend which supports the program and compares the outcomes.
+When the outcomes diverge the program is shrunk to the smallest program which still diverges.
+
+Backends with narrower features than others (smaller cells, fixed memory, fewer instructions) are expected to fail on programs they don't support.
+Their outcome is ignored when they fail with a fault which is a [limitation](../backend/struct.Features.html#method.is_limit) of their features,
+eg. an invalid opcode is only excused when the opcode is outside the backend's instruction set.
+*/
+
+use std::fmt;
+use super::backend::{Backend, Level, Outcome, Status};
+
+/// Simple xorshift random number generator.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+impl Rng {
+	pub fn new(seed: u64) -> Rng {
+		Rng(seed.wrapping_mul(0x9E3779B97F4A7C15) | 1)
+	}
+	pub fn next_u64(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+	/// Returns a random number in `0..n`.
+	pub fn below(&mut self, n: usize) -> usize {
+		(self.next_u64() % n as u64) as usize
+	}
+	/// Returns a random number in `lo..hi`.
+	pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+		lo + (self.next_u64() % (hi - lo) as u64) as i64
+	}
+	/// Returns true one in `n` times.
+	pub fn one_in(&mut self, n: usize) -> bool {
+		self.below(n) == 0
+	}
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Config {
+	pub seed: u64,
+	/// Number of programs to generate.
+	pub iterations: usize,
+	/// Instruction budget per run.
+	pub budget: u64,
+	/// Maximum number of instructions in a generated program.
+	pub max_instrs: usize,
+}
+impl Default for Config {
+	fn default() -> Config {
+		Config { seed: 2019, iterations: 1000, budget: 1000, max_instrs: 16 }
+	}
+}
+
+/// A program on which the backends disagree.
+#[derive(Clone, Debug)]
+pub struct Divergence {
+	pub program: Vec<i64>,
+	pub input: Vec<i64>,
+	/// Name of the backend and its outcome.
+	pub outcomes: Vec<(String, Outcome)>,
+}
+impl fmt::Display for Divergence {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "Program: `{}`", join(&self.program))?;
+		writeln!(f, "Input: `{}`", join(&self.input))?;
+		for (name, outcome) in &self.outcomes {
+			let status = match outcome.status {
+				Status::Fault(fault) => format!("fault: {}", fault),
+				status => format!("{:?}", status),
+			};
+			writeln!(f, "\n{}: {}\n  output: `{}`\n  memory: `{}`", name, status, join(&outcome.output), join(&outcome.memory))?;
+		}
+		Ok(())
+	}
+}
+fn join(values: &[i64]) -> String {
+	values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(",")
+}
+
+//----------------------------------------------------------------
+
+/// Generates a random program and its input.
+///
+/// The program only uses instructions and modes from the given level.
+/// Addresses point inside the program, jump targets are instructions.
+pub fn generate(rng: &mut Rng, level: Level, max_instrs: usize) -> (Vec<i64>, Vec<i64>) {
+	let opcodes = level.opcodes();
+	let modes = level.modes() as usize;
+	let value = |rng: &mut Rng| match level {
+		Level::Day2 => rng.range(0, 100),
+		_ if rng.one_in(8) => rng.range(-1000000, 1000000),
+		_ => rng.range(-50, 50),
+	};
+
+	// Lay out the instructions first to know where they start
+	let count = 1 + rng.below(max_instrs);
+	let mut instrs = Vec::new();
+	let mut starts = Vec::new();
+	let mut len = 0;
+	for _ in 0..count {
+		let opcode = opcodes[rng.below(opcodes.len())];
+		starts.push(len as i64);
+		len += match opcode { 1 | 2 | 7 | 8 => 4, 5 | 6 => 3, _ => 2 };
+		instrs.push(opcode);
+	}
+	starts.push(len as i64);
+	let len = len + 1 + rng.below(8);
+
+	let mut program = Vec::new();
+	let mut inputs = 0;
+	for &opcode in &instrs {
+		// Which arguments are written to, jump targets and otherwise read from
+		let (args, write, target) = match opcode {
+			1 | 2 | 7 | 8 => (3, 3, 0),
+			3 => (1, 1, 0),
+			4 | 9 => (1, 0, 0),
+			_ => (2, 0, 2),
+		};
+		let mut word = opcode;
+		let mut operands = Vec::new();
+		for n in 1..=args {
+			let mut mode = rng.below(modes) as i64;
+			if n == write && mode == 1 {
+				mode = 0;
+			}
+			let operand = match mode {
+				1 if n == target => starts[rng.below(starts.len())],
+				1 => value(rng),
+				_ => rng.below(len) as i64,
+			};
+			word += mode * [100, 1000, 10000][n - 1];
+			operands.push(operand);
+		}
+		if opcode == 3 {
+			inputs += 1;
+		}
+		program.push(word);
+		program.extend(operands);
+	}
+	program.push(99);
+	while program.len() < len {
+		program.push(value(rng));
+	}
+
+	let input = (0..inputs * 2).map(|_| value(rng)).collect();
+	(program, input)
+}
+
+/// Runs the program on the backends and checks if the outcomes diverge.
+pub fn compare(backends: &[&dyn Backend], program: &[i64], input: &[i64], budget: u64) -> Option<Divergence> {
+	let outcomes = backends.iter()
+		.map(|backend| (backend.name().to_string(), backend.run(program, input, budget)))
+		.collect::<Vec<_>>();
+
+	// Ignore backends which ran into their limitations when a more capable backend is present
+	let comparable = backends.iter().zip(&outcomes).filter(|(backend, (_, outcome))| {
+		let narrower = backends.iter().any(|other| !backend.features().covers(&other.features()));
+		match outcome.status {
+			Status::Fault(fault) => !(narrower && backend.features().is_limit(&fault)),
+			_ => true,
+		}
+	}).map(|(_, (_, outcome))| outcome).collect::<Vec<_>>();
+
+	if comparable.windows(2).all(|pair| pair[0] == pair[1]) {
+		return None;
+	}
+	Some(Divergence { program: program.to_vec(), input: input.to_vec(), outcomes })
+}
+
+/// Shrinks the diverging program and input while the backends keep diverging.
+pub fn minimize(backends: &[&dyn Backend], divergence: Divergence, budget: u64) -> Divergence {
+	let mut best = divergence;
+	loop {
+		let mut candidates = Vec::new();
+		for i in 0..best.input.len() {
+			let mut input = best.input.clone();
+			input.remove(i);
+			candidates.push((best.program.clone(), input));
+		}
+		for i in 0..best.program.len() {
+			let mut program = best.program.clone();
+			program.truncate(i);
+			candidates.push((program, best.input.clone()));
+			let mut program = best.program.clone();
+			program.remove(i);
+			candidates.push((program, best.input.clone()));
+			if best.program[i] != 0 {
+				let mut program = best.program.clone();
+				program[i] = 0;
+				candidates.push((program.clone(), best.input.clone()));
+				program[i] = best.program[i] / 2;
+				candidates.push((program, best.input.clone()));
+			}
+		}
+		let current = size(&best.program, &best.input);
+		let smaller = candidates.into_iter()
+			.filter(|(program, input)| size(program, input) < current)
+			.find_map(|(program, input)| compare(backends, &program, &input, budget));
+		match smaller {
+			Some(divergence) => best = divergence,
+			None => return best,
+		}
+	}
+}
+fn size(program: &[i64], input: &[i64]) -> (usize, u64) {
+	let magnitude = program.iter().chain(input).map(|value| value.unsigned_abs()).sum();
+	(program.len() + input.len(), magnitude)
+}
+
+/// Fuzzes the backends against each other.
+///
+/// Every level supported by at least two backends is tested with programs of that level.
+/// Returns the number of programs tested or the minimized divergence.
+pub fn fuzz(backends: &[&dyn Backend], config: &Config) -> Result<usize, Divergence> {
+	let mut rng = Rng::new(config.seed);
+	let levels = [Level::Day2, Level::Day5, Level::Day9].iter().cloned()
+		.filter(|&level| backends.iter().filter(|backend| backend.features().level >= level).count() >= 2)
+		.collect::<Vec<_>>();
+	if levels.is_empty() {
+		return Ok(0);
+	}
+	for _ in 0..config.iterations {
+		let level = levels[rng.below(levels.len())];
+		let (program, input) = generate(&mut rng, level, config.max_instrs);
+		let participants = backends.iter().cloned()
+			.filter(|backend| backend.features().level >= level)
+			.collect::<Vec<_>>();
+		if let Some(divergence) = compare(&participants, &program, &input, config.budget) {
+			return Err(minimize(&participants, divergence, config.budget));
+		}
+	}
+	Ok(config.iterations)
+}
+
+#[test]
+fn test_builtin() {
+	let backends = super::backend::backends();
+	let backends = backends.iter().map(|backend| &**backend).collect::<Vec<_>>();
+	let config = Config { iterations: 2000, ..Config::default() };
+	if let Err(divergence) = fuzz(&backends, &config) {
+		panic!("backends diverge:\n{}", divergence);
+	}
+}
+
+#[test]
+fn test_minimize() {
+	use super::backend::{Day9, Features};
+
+	// Backend which forgets the sign of its output
+	struct Unsigned;
+	impl Backend for Unsigned {
+		fn name(&self) -> &str { "unsigned" }
+		fn features(&self) -> Features { Day9.features() }
+		fn run(&self, program: &[i64], input: &[i64], budget: u64) -> Outcome {
+			let mut outcome = Day9.run(program, input, budget);
+			for value in &mut outcome.output {
+				*value = value.abs();
+			}
+			outcome
+		}
+	}
+
+	let backends: [&dyn Backend; 2] = [&Day9, &Unsigned];
+	let divergence = fuzz(&backends, &Config::default()).expect_err("expected a divergence");
+	assert!(divergence.program.len() <= 3, "not minimal:\n{}", divergence);
+	assert_ne!(divergence.outcomes[0].1.output, divergence.outcomes[1].1.output);
+}
+
+#[test]
+fn test_broken() {
+	use super::{Fault, Intcode, State};
+	use super::backend::{Day5, Day9, Features};
+
+	// Day 5 backend which lost its equals instruction
+	struct Broken;
+	impl Backend for Broken {
+		fn name(&self) -> &str { "broken" }
+		fn features(&self) -> Features { Day5.features() }
+		fn run(&self, program: &[i64], input: &[i64], budget: u64) -> Outcome {
+			let mut vm = Intcode::new(program);
+			vm.input.extend(input);
+			let mut output = Vec::new();
+			let status = loop {
+				if vm.steps >= budget {
+					break Status::OutOfBudget;
+				}
+				let opcode = vm.mem.get(vm.ip).cloned().unwrap_or(0);
+				if opcode % 100 == 8 {
+					break Status::Fault(Fault::InvalidOpcode { ip: vm.ip, opcode });
+				}
+				match vm.step() {
+					Ok(State::Running) => (),
+					Ok(State::Input) => break Status::OutOfInput,
+					Ok(State::Output(value)) => output.push(value),
+					Ok(State::Halted) => break Status::Halted,
+					Err(fault) => break Status::Fault(fault),
+				}
+			};
+			let mut memory = vm.mem;
+			while memory.last() == Some(&0) {
+				memory.pop();
+			}
+			Outcome { status, output, memory }
+		}
+	}
+
+	// The invalid opcode is part of the day 5 instruction set so it must not be excused next to day 9
+	let backends: [&dyn Backend; 2] = [&Day9, &Broken];
+	let divergence = fuzz(&backends, &Config::default()).expect_err("expected a divergence");
+	assert!(matches!(divergence.outcomes[1].1.status, Status::Fault(Fault::InvalidOpcode { opcode, .. }) if opcode % 100 == 8), "{}", divergence);
+}
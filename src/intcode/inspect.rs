@@ -0,0 +1,150 @@
+/*!
+Inspecting Intcode memory.
+
+Dumps memory as a table with disassembled instructions, searches it for sequences of values and shows the differences between two memories,
+eg. the initial image and a machine's memory after running, or two snapshots of a cloned machine.
+*/
+
+use std::fmt;
+use super::Instr;
+
+/// A range of memory formatted as a table of addresses, values and instructions.
+///
+/// Instructions are found by disassembling linearly from the start of the range: cells which decode as an instruction are annotated
+/// and their parameters skipped, all other cells are data.
+#[derive(Copy, Clone, Debug)]
+pub struct Table<'a> {
+	pub mem: &'a [i64],
+	pub start: usize,
+	pub end: usize,
+}
+impl<'a> Table<'a> {
+	pub fn new(mem: &'a [i64], start: usize, end: usize) -> Table<'a> {
+		let end = end.min(mem.len());
+		Table { mem, start: start.min(end), end }
+	}
+}
+impl<'a> fmt::Display for Table<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "address                value  instruction")?;
+		let mut next = self.start;
+		for address in self.start..self.end {
+			write!(f, "{:>7}  {:>19}", address, self.mem[address])?;
+			if address == next {
+				next += 1;
+				if let Ok(instr) = Instr::decode(self.mem, address) {
+					if address + instr.advance() <= self.mem.len() {
+						write!(f, "  {}", instr)?;
+						next = address + instr.advance();
+					}
+				}
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
+
+/// Returns the addresses where the sequence of values starts.
+pub fn search(mem: &[i64], needle: &[i64]) -> Vec<usize> {
+	if needle.is_empty() {
+		return Vec::new();
+	}
+	mem.windows(needle.len()).enumerate()
+		.filter(|&(_, window)| window == needle)
+		.map(|(address, _)| address)
+		.collect()
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Change {
+	pub address: usize,
+	pub old: i64,
+	pub new: i64,
+}
+
+/// The changed cells between two memories.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Diff {
+	pub changes: Vec<Change>,
+}
+impl Diff {
+	pub fn is_empty(&self) -> bool {
+		self.changes.is_empty()
+	}
+}
+impl fmt::Display for Diff {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.changes.is_empty() {
+			return writeln!(f, "Memories are equal.");
+		}
+		writeln!(f, "`{}` cells differ:", self.changes.len())?;
+		for change in &self.changes {
+			writeln!(f, "  address {:>7}: `{}` -> `{}`", change.address, change.old, change.new)?;
+		}
+		Ok(())
+	}
+}
+
+/// Compares two memories, cells beyond the end of the shorter one are zero.
+pub fn diff(old: &[i64], new: &[i64]) -> Diff {
+	let changes = (0..old.len().max(new.len()))
+		.map(|address| Change { address, old: old.get(address).cloned().unwrap_or(0), new: new.get(address).cloned().unwrap_or(0) })
+		.filter(|change| change.old != change.new)
+		.collect();
+	Diff { changes }
+}
+
+/// Asserts that two memories are equal, panicking with their differences.
+#[track_caller]
+pub fn assert_memory<T: Copy + Into<i64>>(actual: &[T], expected: &[T]) {
+	let actual = actual.iter().map(|&value| value.into()).collect::<Vec<i64>>();
+	let expected = expected.iter().map(|&value| value.into()).collect::<Vec<i64>>();
+	if actual.len() != expected.len() {
+		panic!("memory has `{}` cells instead of `{}`, expected -> actual:\n{}", actual.len(), expected.len(), diff(&expected, &actual));
+	}
+	let diff = diff(&expected, &actual);
+	if !diff.is_empty() {
+		panic!("memory differs, expected -> actual:\n{}", diff);
+	}
+}
+
+#[test]
+fn test_table() {
+	let table = Table::new(&[1002,4,3,4,33,0,204,-7,99], 0, 100).to_string();
+	let lines = table.lines().map(str::trim_end).collect::<Vec<_>>();
+	assert_eq!(lines, [
+		"address                value  instruction",
+		"      0                 1002  mul [4], 3, [4]",
+		"      1                    4",
+		"      2                    3",
+		"      3                    4",
+		"      4                   33",
+		"      5                    0",
+		"      6                  204  out [rb-7]",
+		"      7                   -7",
+		"      8                   99  halt",
+	]);
+}
+
+#[test]
+fn test_search_diff() {
+	let mem = [1,2,3,1,2,1,2];
+	assert_eq!(search(&mem, &[1,2]), [0, 3, 5]);
+	assert_eq!(search(&mem, &[2,3,1]), [1]);
+	assert_eq!(search(&mem, &[4]), []);
+
+	let mut vm = super::Intcode::new(&[1,9,10,3,2,3,11,0,99,30,40,50]);
+	let snapshot = vm.clone();
+	vm.run().unwrap();
+	let diff = diff(&snapshot.mem, &vm.mem);
+	assert_eq!(diff.changes, [Change { address: 0, old: 1, new: 3500 }, Change { address: 3, old: 3, new: 70 }]);
+	assert_eq!(diff.to_string(), "`2` cells differ:\n  address       0: `1` -> `3500`\n  address       3: `3` -> `70`\n");
+	assert!(super::inspect::diff(&[1,0], &[1]).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "address       3: `70` -> `71`")]
+fn test_assert_memory() {
+	assert_memory::<u32>(&[3500,9,10,71], &[3500,9,10,70]);
+}
@@ -0,0 +1,147 @@
+/*!
+Loading Intcode programs.
+
+The text format is the puzzle input: comma separated cells.
+Whitespace and newlines between cells are ignored, as is everything after a `#` up to the end of the line.
+A line may end with a comma, but there are no empty cells in between:
+
+```text
+# Outputs its input
+3,0,  4,0,
+99
+```
+
+Large images can be stored in a binary format instead: the magic bytes `ICB1` followed by the cells as 64-bit little-endian integers.
+*/
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::io;
+use std::io::prelude::*;
+
+/// Magic bytes of the binary format.
+pub const MAGIC: &[u8; 4] = b"ICB1";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LoadError {
+	/// A text cell which isn't an integer.
+	InvalidToken { index: usize, line: usize, column: usize, text: String },
+	/// A cell which doesn't fit the interpreter's cell type.
+	OutOfRange { index: usize, value: i64 },
+	/// A binary image whose length isn't a multiple of the cell size.
+	Truncated { len: usize },
+	NotUtf8,
+	Empty,
+}
+impl fmt::Display for LoadError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			LoadError::InvalidToken { index, line, column, text } => write!(f, "invalid cell {} on line {} column {}: `{}`", index, line, column, text),
+			LoadError::OutOfRange { index, value } => write!(f, "cell {} out of range: `{}`", index, value),
+			LoadError::Truncated { len } => write!(f, "truncated binary image of {} bytes", len),
+			LoadError::NotUtf8 => write!(f, "program is neither text nor a binary image"),
+			LoadError::Empty => write!(f, "empty program"),
+		}
+	}
+}
+
+/// Parses a program in the text format.
+pub fn parse(text: &str) -> Result<Vec<i64>, LoadError> {
+	let mut program = Vec::new();
+	for (index, line) in text.lines().enumerate() {
+		let line_nr = index + 1;
+		let line = match line.find('#') {
+			Some(comment) => &line[..comment],
+			None => line,
+		};
+		let tokens = line.split(',').collect::<Vec<_>>();
+		let mut start = 0;
+		for (n, token) in tokens.iter().enumerate() {
+			let column = start + token.len() - token.trim_start().len() + 1;
+			start += token.len() + 1;
+			let token = token.trim();
+			// Only the token after a trailing comma, or of a blank line, may be empty
+			if token.is_empty() && n + 1 == tokens.len() {
+				continue;
+			}
+			match token.parse() {
+				Ok(value) => program.push(value),
+				Err(_) => return Err(LoadError::InvalidToken { index: program.len(), line: line_nr, column, text: token.to_string() }),
+			}
+		}
+	}
+	if program.is_empty() {
+		return Err(LoadError::Empty);
+	}
+	Ok(program)
+}
+
+/// Loads a program in either the binary or the text format.
+pub fn load(bytes: &[u8]) -> Result<Vec<i64>, LoadError> {
+	if !bytes.starts_with(MAGIC) {
+		return parse(std::str::from_utf8(bytes).map_err(|_| LoadError::NotUtf8)?);
+	}
+	let cells = bytes[MAGIC.len()..].chunks_exact(8);
+	if !cells.remainder().is_empty() {
+		return Err(LoadError::Truncated { len: bytes.len() });
+	}
+	if cells.len() == 0 {
+		return Err(LoadError::Empty);
+	}
+	Ok(cells.map(|cell| i64::from_le_bytes(<[u8; 8]>::try_from(cell).unwrap())).collect())
+}
+
+/// Encodes a program in the binary format.
+pub fn to_binary(program: &[i64]) -> Vec<u8> {
+	let mut bytes = MAGIC.to_vec();
+	for value in program {
+		bytes.extend_from_slice(&value.to_le_bytes());
+	}
+	bytes
+}
+
+/// Converts the cells to a narrower type.
+pub fn cells<T: TryFrom<i64>>(program: &[i64]) -> Result<Vec<T>, LoadError> {
+	program.iter().enumerate()
+		.map(|(index, &value)| T::try_from(value).map_err(|_| LoadError::OutOfRange { index, value }))
+		.collect()
+}
+
+/// Loads the program from stdin, panicking with the error.
+pub fn stdin<T: TryFrom<i64>>() -> Vec<T> {
+	let mut bytes = Vec::new();
+	let _ = io::stdin().read_to_end(&mut bytes).expect("error reading input");
+	load(&bytes).and_then(|program| cells(&program))
+		.unwrap_or_else(|err| panic!("error loading program: {}", err))
+}
+
+/// Loads the program from its text, panicking with the error.
+pub fn text<T: TryFrom<i64>>(text: &str) -> Vec<T> {
+	parse(text).and_then(|program| cells(&program))
+		.unwrap_or_else(|err| panic!("error loading program: {}", err))
+}
+
+#[test]
+fn test_parse() {
+	assert_eq!(parse("1,9,10,3,2,3,11,0,99,30,40,50\n"), Ok(vec![1,9,10,3,2,3,11,0,99,30,40,50]));
+	assert_eq!(parse("# Echo\n3,0,  4,0, # comment\n\n 99,\n"), Ok(vec![3,0,4,0,99]));
+	assert_eq!(parse("1,2\n3,-4"), Ok(vec![1,2,3,-4]));
+	assert_eq!(parse("1,2,\n3,x4,5"), Err(LoadError::InvalidToken { index: 3, line: 2, column: 3, text: "x4".into() }));
+	assert_eq!(parse("1 2"), Err(LoadError::InvalidToken { index: 0, line: 1, column: 1, text: "1 2".into() }));
+	assert_eq!(parse("1,,2"), Err(LoadError::InvalidToken { index: 1, line: 1, column: 3, text: "".into() }));
+	assert_eq!(parse("1,2\n , 3"), Err(LoadError::InvalidToken { index: 2, line: 2, column: 2, text: "".into() }));
+	assert_eq!(parse("1,2, ,\n"), Err(LoadError::InvalidToken { index: 2, line: 1, column: 6, text: "".into() }));
+	assert_eq!(parse(" \n# nothing\n"), Err(LoadError::Empty));
+}
+
+#[test]
+fn test_binary() {
+	let program = [109,1,204,-1,1125899906842624,99];
+	let bytes = to_binary(&program);
+	assert_eq!(bytes.len(), 4 + 8 * program.len());
+	assert_eq!(load(&bytes), Ok(program.to_vec()));
+	assert_eq!(load(&bytes[..bytes.len() - 1]), Err(LoadError::Truncated { len: bytes.len() - 1 }));
+	assert_eq!(load(MAGIC), Err(LoadError::Empty));
+	assert_eq!(load(b"1,2,\xff"), Err(LoadError::NotUtf8));
+	assert_eq!(cells::<u32>(&[1, -2]), Err(LoadError::OutOfRange { index: 1, value: -2 }));
+}
@@ -0,0 +1,439 @@
+/*!
+Intcode computer.
+
+The instruction set is the complete one from day 9 with relative mode and 64-bit cells.
+Unlike the interpreters in the day binaries the machine is resumable: it stops whenever it produces an output or needs an input which hasn't been provided yet.
+*/
+
+use std::collections::VecDeque;
+use std::fmt;
+
+pub mod backend;
+pub mod compiler;
+pub mod conformance;
+pub mod ext;
+pub mod fuzz;
+pub mod inspect;
+pub mod load;
+pub mod opt;
+pub mod replay;
+pub mod smc;
+
+pub use self::ext::{Extension, Registry};
+
+/// Memory is grown on demand up to this many cells.
+pub const MAX_MEMORY: usize = 0x100000;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Arg {
+	Position(i64),
+	Immediate(i64),
+	Relative(i64),
+}
+impl Arg {
+	pub fn decode(mode: u8, arg: i64) -> Option<Arg> {
+		match mode {
+			0 => Some(Arg::Position(arg)),
+			1 => Some(Arg::Immediate(arg)),
+			2 => Some(Arg::Relative(arg)),
+			_ => None,
+		}
+	}
+	pub fn mode(&self) -> i64 {
+		match self {
+			Arg::Position(_) => 0,
+			Arg::Immediate(_) => 1,
+			Arg::Relative(_) => 2,
+		}
+	}
+	pub fn value(&self) -> i64 {
+		match *self {
+			Arg::Position(value) => value,
+			Arg::Immediate(value) => value,
+			Arg::Relative(value) => value,
+		}
+	}
+}
+impl fmt::Display for Arg {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Arg::Position(address) => write!(f, "[{}]", address),
+			Arg::Immediate(value) => write!(f, "{}", value),
+			Arg::Relative(offset) => write!(f, "[rb{:+}]", offset),
+		}
+	}
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Instr {
+	Add { src1: Arg, src2: Arg, dest: Arg },
+	Mul { src1: Arg, src2: Arg, dest: Arg },
+	Input { dest: Arg },
+	Output { src: Arg },
+	JumpIfTrue { arg: Arg, target: Arg },
+	JumpIfFalse { arg: Arg, target: Arg },
+	LessThan { src1: Arg, src2: Arg, dest: Arg },
+	Equals { src1: Arg, src2: Arg, dest: Arg },
+	AdjustRelBase { value: Arg },
+	Halt,
+}
+impl Instr {
+	pub fn decode(intcode: &[i64], ip: usize) -> Result<Instr, Fault> {
+		let fetch = |offset: usize| intcode.get(ip + offset).cloned().unwrap_or(0);
+		let opcode = fetch(0);
+		let arg = |n: usize| {
+			let mode = opcode / [100, 1000, 10000][n - 1] % 10;
+			Arg::decode(mode as u8, fetch(n)).ok_or(Fault::InvalidMode { ip, opcode })
+		};
+		match opcode % 100 {
+			1 => Ok(Instr::Add { src1: arg(1)?, src2: arg(2)?, dest: arg(3)? }),
+			2 => Ok(Instr::Mul { src1: arg(1)?, src2: arg(2)?, dest: arg(3)? }),
+			3 => Ok(Instr::Input { dest: arg(1)? }),
+			4 => Ok(Instr::Output { src: arg(1)? }),
+			5 => Ok(Instr::JumpIfTrue { arg: arg(1)?, target: arg(2)? }),
+			6 => Ok(Instr::JumpIfFalse { arg: arg(1)?, target: arg(2)? }),
+			7 => Ok(Instr::LessThan { src1: arg(1)?, src2: arg(2)?, dest: arg(3)? }),
+			8 => Ok(Instr::Equals { src1: arg(1)?, src2: arg(2)?, dest: arg(3)? }),
+			9 => Ok(Instr::AdjustRelBase { value: arg(1)? }),
+			99 => Ok(Instr::Halt),
+			_ => Err(Fault::InvalidOpcode { ip, opcode }),
+		}
+	}
+	/// Returns the opcode, without modes, and the arguments of the instruction.
+	pub fn parts(&self) -> (i64, Vec<Arg>) {
+		match *self {
+			Instr::Add { src1, src2, dest } => (1, vec![src1, src2, dest]),
+			Instr::Mul { src1, src2, dest } => (2, vec![src1, src2, dest]),
+			Instr::Input { dest } => (3, vec![dest]),
+			Instr::Output { src } => (4, vec![src]),
+			Instr::JumpIfTrue { arg, target } => (5, vec![arg, target]),
+			Instr::JumpIfFalse { arg, target } => (6, vec![arg, target]),
+			Instr::LessThan { src1, src2, dest } => (7, vec![src1, src2, dest]),
+			Instr::Equals { src1, src2, dest } => (8, vec![src1, src2, dest]),
+			Instr::AdjustRelBase { value } => (9, vec![value]),
+			Instr::Halt => (99, vec![]),
+		}
+	}
+	/// Encodes the instruction back into its opcode and parameters.
+	pub fn encode(&self, out: &mut Vec<i64>) {
+		let (opcode, args) = self.parts();
+		let modes = args.iter().rev().fold(0, |modes, arg| modes * 10 + arg.mode());
+		out.push(modes * 100 + opcode);
+		out.extend(args.iter().map(|arg| arg.value()));
+	}
+	pub fn advance(&self) -> usize {
+		match self {
+			Instr::Add { .. } => 4,
+			Instr::Mul { .. } => 4,
+			Instr::Input { .. } => 2,
+			Instr::Output { .. } => 2,
+			Instr::JumpIfTrue { .. } => 3,
+			Instr::JumpIfFalse { .. } => 3,
+			Instr::LessThan { .. } => 4,
+			Instr::Equals { .. } => 4,
+			Instr::AdjustRelBase { .. } => 2,
+			Instr::Halt => 1,
+		}
+	}
+	pub fn mnemonic(&self) -> &'static str {
+		match self {
+			Instr::Add { .. } => "add",
+			Instr::Mul { .. } => "mul",
+			Instr::Input { .. } => "in",
+			Instr::Output { .. } => "out",
+			Instr::JumpIfTrue { .. } => "jt",
+			Instr::JumpIfFalse { .. } => "jf",
+			Instr::LessThan { .. } => "lt",
+			Instr::Equals { .. } => "eq",
+			Instr::AdjustRelBase { .. } => "arb",
+			Instr::Halt => "halt",
+		}
+	}
+}
+/// Disassembles the instruction, eg. `add [4], 3, [rb+1]`.
+impl fmt::Display for Instr {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.mnemonic())?;
+		for (index, arg) in self.parts().1.iter().enumerate() {
+			write!(f, "{}{}", if index == 0 { " " } else { ", " }, arg)?;
+		}
+		Ok(())
+	}
+}
+
+/// Reasons an Intcode program can crash.
+///
+/// The `ip` is the address of the faulting instruction.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Fault {
+	InvalidOpcode { ip: usize, opcode: i64 },
+	InvalidMode { ip: usize, opcode: i64 },
+	ImmediateWrite { ip: usize },
+	InvalidAddress { ip: usize, address: i64 },
+	OutOfMemory { ip: usize, address: i64 },
+	Overflow { ip: usize },
+	MissingInput { ip: usize },
+}
+impl Fault {
+	pub fn ip(&self) -> usize {
+		match *self {
+			Fault::InvalidOpcode { ip, .. } => ip,
+			Fault::InvalidMode { ip, .. } => ip,
+			Fault::ImmediateWrite { ip } => ip,
+			Fault::InvalidAddress { ip, .. } => ip,
+			Fault::OutOfMemory { ip, .. } => ip,
+			Fault::Overflow { ip } => ip,
+			Fault::MissingInput { ip } => ip,
+		}
+	}
+}
+impl fmt::Display for Fault {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Fault::InvalidOpcode { ip, opcode } => write!(f, "invalid opcode {} at {}", opcode, ip),
+			Fault::InvalidMode { ip, opcode } => write!(f, "invalid mode in opcode {} at {}", opcode, ip),
+			Fault::ImmediateWrite { ip } => write!(f, "write to immediate argument at {}", ip),
+			Fault::InvalidAddress { ip, address } => write!(f, "invalid address {} at {}", address, ip),
+			Fault::OutOfMemory { ip, address } => write!(f, "address {} out of memory at {}", address, ip),
+			Fault::Overflow { ip } => write!(f, "arithmetic overflow at {}", ip),
+			Fault::MissingInput { ip } => write!(f, "missing input at {}", ip),
+		}
+	}
+}
+
+/// Why the machine stopped running.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum State {
+	/// Executed an instruction and can continue.
+	Running,
+	/// Blocked on an input instruction, push to the input queue and continue.
+	Input,
+	/// Produced an output value.
+	Output(i64),
+	/// Reached the halt instruction.
+	Halted,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Intcode {
+	pub mem: Vec<i64>,
+	pub ip: usize,
+	pub rel_base: i64,
+	/// Number of instructions executed so far.
+	pub steps: u64,
+	/// Queued values for the input instruction.
+	pub input: VecDeque<i64>,
+	/// Tracks writes into code when enabled.
+	pub smc: Option<smc::CodeTracker>,
+}
+impl Intcode {
+	pub fn new(program: &[i64]) -> Intcode {
+		Intcode { mem: program.to_vec(), ..Intcode::default() }
+	}
+
+	/// Enables tracking of self-modifying code.
+	pub fn track_code(&mut self) {
+		self.smc = Some(smc::CodeTracker::default());
+	}
+
+	/// Executes a single instruction.
+	pub fn step(&mut self) -> Result<State, Fault> {
+		self.step_with(&mut ())
+	}
+
+	/// Executes a single instruction, unknown opcodes are handed to the extension.
+	pub fn step_with(&mut self, ext: &mut dyn Extension) -> Result<State, Fault> {
+		let ip = self.ip;
+		let instr = match Instr::decode(&self.mem, ip) {
+			Err(Fault::InvalidOpcode { opcode, .. }) if opcode >= 0 && ext.arity(opcode % 100).is_some() => {
+				return self.custom(ext, opcode);
+			},
+			instr => instr?,
+		};
+		if let Some(smc) = &mut self.smc {
+			smc.execute(ip, instr.advance());
+		}
+		let mut next = ip + instr.advance();
+		let mut state = State::Running;
+		match instr {
+			Instr::Add { src1, src2, dest } => {
+				let value = self.read(src1)?.checked_add(self.read(src2)?).ok_or(Fault::Overflow { ip })?;
+				self.write(dest, value)?;
+			},
+			Instr::Mul { src1, src2, dest } => {
+				let value = self.read(src1)?.checked_mul(self.read(src2)?).ok_or(Fault::Overflow { ip })?;
+				self.write(dest, value)?;
+			},
+			Instr::Input { dest } => {
+				match self.input.front() {
+					Some(&value) => {
+						self.write(dest, value)?;
+						self.input.pop_front();
+					},
+					None => return Ok(State::Input),
+				}
+			},
+			Instr::Output { src } => {
+				state = State::Output(self.read(src)?);
+			},
+			Instr::JumpIfTrue { arg, target } => {
+				if self.read(arg)? != 0 {
+					next = self.jump(target)?;
+				}
+			},
+			Instr::JumpIfFalse { arg, target } => {
+				if self.read(arg)? == 0 {
+					next = self.jump(target)?;
+				}
+			},
+			Instr::LessThan { src1, src2, dest } => {
+				let value = if self.read(src1)? < self.read(src2)? { 1 } else { 0 };
+				self.write(dest, value)?;
+			},
+			Instr::Equals { src1, src2, dest } => {
+				let value = if self.read(src1)? == self.read(src2)? { 1 } else { 0 };
+				self.write(dest, value)?;
+			},
+			Instr::AdjustRelBase { value } => {
+				self.rel_base = self.rel_base.checked_add(self.read(value)?).ok_or(Fault::Overflow { ip })?;
+			},
+			Instr::Halt => return Ok(State::Halted),
+		}
+		self.ip = next;
+		self.steps += 1;
+		Ok(state)
+	}
+
+	/// Runs until the machine halts, produces an output or blocks on input.
+	pub fn run(&mut self) -> Result<State, Fault> {
+		self.run_with(&mut ())
+	}
+
+	/// Runs until the machine halts, produces an output or blocks on input, unknown opcodes are handed to the extension.
+	pub fn run_with(&mut self, ext: &mut dyn Extension) -> Result<State, Fault> {
+		loop {
+			match self.step_with(ext)? {
+				State::Running => (),
+				state => return Ok(state),
+			}
+		}
+	}
+
+	fn custom(&mut self, ext: &mut dyn Extension, opcode: i64) -> Result<State, Fault> {
+		let ip = self.ip;
+		let arity = ext.arity(opcode % 100).unwrap_or(0);
+		let mut args = Vec::with_capacity(arity);
+		let mut modes = opcode / 100;
+		for n in 1..=arity {
+			let arg = self.mem.get(ip + n).cloned().unwrap_or(0);
+			args.push(Arg::decode((modes % 10) as u8, arg).ok_or(Fault::InvalidMode { ip, opcode })?);
+			modes /= 10;
+		}
+		if let Some(smc) = &mut self.smc {
+			smc.execute(ip, 1 + arity);
+		}
+		let state = ext.execute(self, opcode % 100, &args)?;
+		// Like the built-in instructions, blocking on input and halting don't advance
+		if let State::Running | State::Output(_) = state {
+			if self.ip == ip {
+				self.ip = ip + 1 + arity;
+			}
+			self.steps += 1;
+		}
+		Ok(state)
+	}
+
+	/// Resolves the address of a position or relative argument.
+	pub fn address(&self, arg: Arg) -> Result<usize, Fault> {
+		let address = match arg {
+			Arg::Position(arg) => arg,
+			Arg::Immediate(_) => return Err(Fault::ImmediateWrite { ip: self.ip }),
+			Arg::Relative(arg) => self.rel_base.checked_add(arg).ok_or(Fault::Overflow { ip: self.ip })?,
+		};
+		if address < 0 {
+			return Err(Fault::InvalidAddress { ip: self.ip, address });
+		}
+		if address as u64 >= MAX_MEMORY as u64 {
+			return Err(Fault::OutOfMemory { ip: self.ip, address });
+		}
+		Ok(address as usize)
+	}
+	pub fn read(&self, arg: Arg) -> Result<i64, Fault> {
+		if let Arg::Immediate(value) = arg {
+			return Ok(value);
+		}
+		let address = self.address(arg)?;
+		Ok(self.mem.get(address).cloned().unwrap_or(0))
+	}
+	pub fn write(&mut self, arg: Arg, value: i64) -> Result<(), Fault> {
+		let address = self.address(arg)?;
+		if address >= self.mem.len() {
+			self.mem.resize(address + 1, 0);
+		}
+		if let Some(smc) = &mut self.smc {
+			smc.write(self.ip, address, self.mem[address], value);
+		}
+		self.mem[address] = value;
+		Ok(())
+	}
+	fn jump(&self, target: Arg) -> Result<usize, Fault> {
+		let target = self.read(target)?;
+		if target < 0 {
+			return Err(Fault::InvalidAddress { ip: self.ip, address: target });
+		}
+		Ok(target as usize)
+	}
+}
+
+/// Runs the program to completion, returns all its outputs.
+pub fn execute(program: &[i64], input: &[i64]) -> Result<Vec<i64>, Fault> {
+	let mut vm = Intcode::new(program);
+	vm.input.extend(input);
+	let mut output = Vec::new();
+	loop {
+		match vm.run()? {
+			State::Running => (),
+			State::Input => return Err(Fault::MissingInput { ip: vm.ip }),
+			State::Output(value) => output.push(value),
+			State::Halted => return Ok(output),
+		}
+	}
+}
+
+#[test]
+fn test_quine() {
+	let program = [109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99];
+	assert_eq!(execute(&program, &[]), Ok(program.to_vec()));
+}
+
+#[test]
+fn test_resume() {
+	let mut vm = Intcode::new(&[3,9,1001,9,1,9,4,9,99,0]);
+	assert_eq!(vm.run(), Ok(State::Input));
+	vm.input.push_back(41);
+	assert_eq!(vm.run(), Ok(State::Output(42)));
+	assert_eq!(vm.run(), Ok(State::Halted));
+	assert_eq!(vm.steps, 3);
+}
+
+#[test]
+fn test_encode() {
+	let program = [1002,4,3,4,21107,3,4,10,204,-7,99];
+	let mut code = Vec::new();
+	let mut ip = 0;
+	while ip < program.len() {
+		let instr = Instr::decode(&program, ip).unwrap();
+		instr.encode(&mut code);
+		ip += instr.advance();
+	}
+	assert_eq!(code, program);
+}
+
+#[test]
+fn test_faults() {
+	assert_eq!(execute(&[1,0,0,0,98], &[]), Err(Fault::InvalidOpcode { ip: 4, opcode: 98 }));
+	assert_eq!(execute(&[301,0,0,0,99], &[]), Err(Fault::InvalidMode { ip: 0, opcode: 301 }));
+	assert_eq!(execute(&[11101,1,1,0,99], &[]), Err(Fault::ImmediateWrite { ip: 0 }));
+	assert_eq!(execute(&[1105,1,-1], &[]), Err(Fault::InvalidAddress { ip: 0, address: -1 }));
+	assert_eq!(execute(&[3,0,99], &[]), Err(Fault::MissingInput { ip: 0 }));
+}
@@ -0,0 +1,573 @@
+/*!
+Peephole optimizer for Intcode program images.
+
+The reachable code is found by following the control flow from address 0.
+
+The rewrites which keep every instruction at its place are applied to all programs:
+
+* Arithmetic and comparisons on two immediates are folded into a constant store (`Add` with zero).
+* `Mul` by 1 or 0 is reduced to the cheaper `Add`.
+* Jumps to unconditional jumps, and over instructions which do nothing, are threaded to their final target.
+
+Instructions whose cells are referenced by position mode arguments, ie. code which is read or patched, are never rewritten.
+Relative mode and indirect jumps can't be followed statically, the rewrites assume they don't touch the middle of reachable instructions.
+[`optimize_checked`](fn.optimize_checked.html) confirms this by running the program on actual inputs with [code tracking](../smc/index.html)
+and leaves it unchanged when it writes into executed code; [`validate`](fn.validate.html) compares the result against the original.
+
+Removing cells shifts the remaining ones and is only safe when the behaviour of the program is fully determined by its image:
+no self-modifying code, no reads of code as data, no indirect jumps and no relative mode (its addresses are only known at runtime).
+For programs which pass these checks:
+
+* Instructions which do nothing are removed: moving a value onto itself, jumps which are never taken or jump to the next instruction.
+* Cells which are neither reachable code nor referenced as data are removed.
+
+All addresses and jump targets are relocated accordingly.
+Programs with overlapping instructions are left unchanged.
+*/
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use super::{Arg, Instr, Intcode, State};
+use super::smc::CodeWrite;
+
+/// Static analysis of the reachable code.
+#[derive(Clone, Debug, Default)]
+pub struct Analysis {
+	/// Reachable instructions by their address.
+	pub instrs: BTreeMap<usize, Instr>,
+	/// Cells which are part of a reachable instruction.
+	pub code: Vec<bool>,
+	/// Cells referenced by position mode arguments.
+	pub data: Vec<bool>,
+	pub indirect_jumps: bool,
+	pub self_modifying: bool,
+	pub code_reads: bool,
+	pub relative: bool,
+	pub overlapping: bool,
+	/// Control flow runs into an invalid instruction or address.
+	pub faulting: bool,
+}
+impl Analysis {
+	/// Returns if cells can be safely removed from the program.
+	pub fn is_static(&self) -> bool {
+		self.reasons().is_empty()
+	}
+	/// Returns why cells can't be removed from the program.
+	pub fn reasons(&self) -> Vec<&'static str> {
+		let checks = [
+			(self.indirect_jumps, "indirect jumps"),
+			(self.self_modifying, "self-modifying code"),
+			(self.code_reads, "reads code as data"),
+			(self.relative, "relative mode"),
+			(self.overlapping, "overlapping instructions"),
+			(self.faulting, "faulting code"),
+		];
+		checks.iter().filter(|check| check.0).map(|check| check.1).collect()
+	}
+}
+
+fn dest(instr: &Instr) -> Option<Arg> {
+	match *instr {
+		Instr::Add { dest, .. } | Instr::Mul { dest, .. } => Some(dest),
+		Instr::LessThan { dest, .. } | Instr::Equals { dest, .. } => Some(dest),
+		Instr::Input { dest } => Some(dest),
+		_ => None,
+	}
+}
+
+pub fn analyze(program: &[i64]) -> Analysis {
+	let mut analysis = Analysis {
+		code: vec![false; program.len()],
+		data: vec![false; program.len()],
+		..Analysis::default()
+	};
+
+	// Follow the control flow
+	let mut undecodable = Vec::new();
+	let mut pending = vec![0];
+	while let Some(ip) = pending.pop() {
+		if analysis.instrs.contains_key(&ip) {
+			continue;
+		}
+		let instr = match Instr::decode(program, ip) {
+			Ok(instr) if ip + instr.advance() <= program.len() => instr,
+			_ => {
+				undecodable.push(ip);
+				continue;
+			},
+		};
+		let next = ip + instr.advance();
+		for cell in &mut analysis.code[ip..next] {
+			analysis.overlapping |= *cell;
+			*cell = true;
+		}
+		match instr {
+			Instr::Halt => (),
+			Instr::JumpIfTrue { arg, target } | Instr::JumpIfFalse { arg, target } => {
+				let taken = match (instr, arg) {
+					(Instr::JumpIfTrue { .. }, Arg::Immediate(value)) => Some(value != 0),
+					(_, Arg::Immediate(value)) => Some(value == 0),
+					_ => None,
+				};
+				if taken != Some(false) {
+					match target {
+						Arg::Immediate(target) if target >= 0 => pending.push(target as usize),
+						Arg::Immediate(_) => analysis.faulting = true,
+						_ => analysis.indirect_jumps = true,
+					}
+				}
+				if taken != Some(true) {
+					pending.push(next);
+				}
+			},
+			_ => pending.push(next),
+		}
+		analysis.instrs.insert(ip, instr);
+	}
+
+	// Check what the arguments refer to
+	for instr in analysis.instrs.values() {
+		let dest = dest(instr);
+		for arg in instr.parts().1 {
+			match arg {
+				Arg::Position(address) if address < 0 => analysis.faulting = true,
+				Arg::Position(address) => {
+					let address = address as usize;
+					if analysis.code.get(address) == Some(&true) {
+						if Some(arg) == dest {
+							analysis.self_modifying = true;
+						}
+						else {
+							analysis.code_reads = true;
+						}
+					}
+					// Invalid instructions which are patched before being executed
+					if let Some(index) = undecodable.iter().position(|&ip| ip == address) {
+						if Some(arg) == dest {
+							undecodable.swap_remove(index);
+							analysis.self_modifying = true;
+						}
+					}
+					if address < program.len() {
+						analysis.data[address] = true;
+					}
+				},
+				Arg::Relative(_) => analysis.relative = true,
+				Arg::Immediate(_) => (),
+			}
+		}
+		if let Instr::AdjustRelBase { .. } = instr {
+			analysis.relative = true;
+		}
+	}
+	analysis.faulting |= !undecodable.is_empty();
+
+	analysis
+}
+
+/// What the optimizer did.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+	pub analysis: Analysis,
+	pub folded: usize,
+	pub reduced: usize,
+	pub threaded: usize,
+	pub nops: usize,
+	pub unreachable: usize,
+	pub before: usize,
+	pub after: usize,
+	/// Writes into executed code found by [`optimize_checked`](fn.optimize_checked.html).
+	pub code_writes: Vec<CodeWrite>,
+}
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if !self.code_writes.is_empty() {
+			return write!(f, "The program is left unchanged, it wrote `{}` times into its executed code.", self.code_writes.len());
+		}
+		if self.analysis.overlapping {
+			return write!(f, "The program is left unchanged, it has overlapping instructions.");
+		}
+		if !self.analysis.is_static() {
+			return write!(f, "Folded `{}` constants, reduced `{}` multiplications and threaded `{}` jumps in place.\n\
+				No cells are removed, the program has {}.",
+				self.folded, self.reduced, self.threaded, self.analysis.reasons().join(", "));
+		}
+		write!(f, "Folded `{}` constants, reduced `{}` multiplications, threaded `{}` jumps, removed `{}` no-ops and `{}` unreachable cells.\n\
+			The image shrunk from `{}` to `{}` cells.",
+			self.folded, self.reduced, self.threaded, self.nops, self.unreachable, self.before, self.after)
+	}
+}
+
+/// Rewrites a single instruction into one of the same length.
+fn rewrite(instr: Instr, report: &mut Report) -> Instr {
+	use self::Arg::Immediate as Imm;
+	let store = |value: i64, dest: Arg| Instr::Add { src1: Imm(value), src2: Imm(0), dest };
+
+	let folded = match instr {
+		Instr::Add { src1: Imm(a), src2: Imm(b), dest } if b != 0 => a.checked_add(b).map(|value| store(value, dest)),
+		Instr::Mul { src1: Imm(a), src2: Imm(b), dest } => a.checked_mul(b).map(|value| store(value, dest)),
+		Instr::LessThan { src1: Imm(a), src2: Imm(b), dest } => Some(store((a < b) as i64, dest)),
+		Instr::Equals { src1: Imm(a), src2: Imm(b), dest } => Some(store((a == b) as i64, dest)),
+		_ => None,
+	};
+	let instr = match folded {
+		Some(instr) => {
+			report.folded += 1;
+			instr
+		},
+		None => instr,
+	};
+
+	let reduced = match instr {
+		Instr::Mul { src1, src2: Imm(1), dest } | Instr::Mul { src1: Imm(1), src2: src1, dest } => Some(Instr::Add { src1, src2: Imm(0), dest }),
+		Instr::Mul { src2: Imm(0), dest, .. } | Instr::Mul { src1: Imm(0), dest, .. } => Some(store(0, dest)),
+		_ => None,
+	};
+	let instr = match reduced {
+		Some(instr) => {
+			report.reduced += 1;
+			instr
+		},
+		None => instr,
+	};
+
+	match instr {
+		// Normalize unconditional jumps to simplify threading
+		Instr::JumpIfFalse { arg: Imm(0), target } => Instr::JumpIfTrue { arg: Imm(1), target },
+		instr => instr,
+	}
+}
+
+/// Returns if the instruction does nothing.
+fn is_nop(instr: &Instr, next: usize) -> bool {
+	use self::Arg::{Immediate as Imm, Position as Pos};
+	match *instr {
+		Instr::Add { src1: Pos(src), src2: Imm(0), dest: Pos(dest) } if src == dest => true,
+		Instr::Add { src1: Imm(0), src2: Pos(src), dest: Pos(dest) } if src == dest => true,
+		Instr::JumpIfTrue { arg: Imm(0), .. } => true,
+		Instr::JumpIfFalse { arg: Imm(value), .. } if value != 0 => true,
+		Instr::JumpIfTrue { target: Imm(target), .. } | Instr::JumpIfFalse { target: Imm(target), .. } => target as usize == next,
+		_ => false,
+	}
+}
+
+/// Optimizes the program image.
+///
+/// Rewrites are repeated until the image no longer changes.
+pub fn optimize(program: &[i64]) -> (Vec<i64>, Report) {
+	let mut report = Report { before: program.len(), ..Report::default() };
+	let mut image = program.to_vec();
+	loop {
+		report.analysis = analyze(&image);
+		if report.analysis.overlapping {
+			break;
+		}
+		let remove = report.analysis.is_static();
+		let next = pass(&image, remove, &mut report);
+		if next == image {
+			break;
+		}
+		image = next;
+	}
+	report.after = image.len();
+	(image, report)
+}
+
+/// Runs the program on every input with code tracking, returns the writes into executed code.
+pub fn code_writes(program: &[i64], inputs: &[Vec<i64>], budget: u64) -> Vec<CodeWrite> {
+	let mut writes = Vec::new();
+	for input in inputs {
+		let mut vm = Intcode::new(program);
+		vm.track_code();
+		vm.input.extend(input);
+		while vm.steps < budget {
+			match vm.step() {
+				Ok(State::Running) | Ok(State::Output(_)) => (),
+				_ => break,
+			}
+		}
+		writes.extend(vm.smc.take().map(|smc| smc.writes).unwrap_or_default());
+	}
+	writes
+}
+
+/// Optimizes the program image after confirming it doesn't write into its executed code on any of the inputs.
+///
+/// Programs which do are left unchanged, their writes are in the report.
+pub fn optimize_checked(program: &[i64], inputs: &[Vec<i64>], budget: u64) -> (Vec<i64>, Report) {
+	let code_writes = code_writes(program, inputs, budget);
+	if !code_writes.is_empty() {
+		let report = Report { analysis: analyze(program), before: program.len(), after: program.len(), code_writes, ..Report::default() };
+		return (program.to_vec(), report);
+	}
+	optimize(program)
+}
+
+/// Rewrites the instructions, removes cells when `remove` is set and otherwise keeps everything in place.
+fn pass(program: &[i64], remove: bool, report: &mut Report) -> Vec<i64> {
+	let analysis = &report.analysis.clone();
+
+	// Instructions which are read or patched as data are left alone, nops are `None` when they're removed
+	let mut rewritten = BTreeMap::new();
+	for (&ip, &instr) in &analysis.instrs {
+		let next = ip + instr.advance();
+		if analysis.data[ip..next].contains(&true) {
+			continue;
+		}
+		let instr = rewrite(instr, report);
+		rewritten.insert(ip, if remove && is_nop(&instr, next) { None } else { Some(instr) });
+	}
+
+	// Thread jumps through unconditional jumps and removed instructions
+	let starts = rewritten.keys().cloned().collect::<Vec<_>>();
+	for ip in starts {
+		let target = match rewritten[&ip] {
+			Some(Instr::JumpIfTrue { target: Arg::Immediate(target), .. }) => target as usize,
+			Some(Instr::JumpIfFalse { target: Arg::Immediate(target), .. }) => target as usize,
+			_ => continue,
+		};
+		let mut threaded = target;
+		let mut visited = HashSet::new();
+		loop {
+			// Infinite loop, leave it alone
+			if !visited.insert(threaded) {
+				threaded = target;
+				break;
+			}
+			match rewritten.get(&threaded) {
+				Some(Some(Instr::JumpIfTrue { arg: Arg::Immediate(value), target: Arg::Immediate(next) })) if *value != 0 => threaded = *next as usize,
+				Some(Some(instr)) if is_nop(instr, threaded + instr.advance()) => threaded += instr.advance(),
+				Some(None) => threaded += analysis.instrs[&threaded].advance(),
+				_ => break,
+			}
+		}
+		if threaded != target {
+			report.threaded += 1;
+			let target = Arg::Immediate(threaded as i64);
+			let instr = rewritten.get_mut(&ip).unwrap();
+			*instr = match *instr {
+				Some(Instr::JumpIfTrue { arg, .. }) => Some(Instr::JumpIfTrue { arg, target }),
+				Some(Instr::JumpIfFalse { arg, .. }) => Some(Instr::JumpIfFalse { arg, target }),
+				instr => instr,
+			};
+		}
+	}
+	if !remove {
+		let mut image = program.to_vec();
+		for (&ip, instr) in &rewritten {
+			let mut cells = Vec::new();
+			instr.expect("nops are kept in place").encode(&mut cells);
+			image[ip..ip + cells.len()].copy_from_slice(&cells);
+		}
+		return image;
+	}
+	report.nops += rewritten.values().filter(|instr| instr.is_none()).count();
+
+	// Keep the remaining code and any referenced data
+	let mut keep = vec![false; program.len()];
+	for (&ip, instr) in &rewritten {
+		if instr.is_some() {
+			for cell in &mut keep[ip..ip + analysis.instrs[&ip].advance()] {
+				*cell = true;
+			}
+		}
+	}
+	for (address, cell) in keep.iter_mut().enumerate() {
+		if !analysis.code[address] && analysis.data[address] {
+			*cell = true;
+		}
+		else if !analysis.code[address] {
+			report.unreachable += 1;
+		}
+	}
+
+	// Relocate addresses to the new layout
+	let mut relocation = Vec::with_capacity(program.len());
+	let mut len = 0;
+	for &kept in &keep {
+		relocation.push(len as i64);
+		len += kept as usize;
+	}
+	let relocate = |address: i64| match relocation.get(address as usize) {
+		Some(&address) => address,
+		None => address - program.len() as i64 + len as i64,
+	};
+
+	let mut image = Vec::with_capacity(len);
+	let mut address = 0;
+	while address < program.len() {
+		match rewritten.get(&address) {
+			Some(Some(instr)) => {
+				let start = image.len();
+				instr.encode(&mut image);
+				let target = match instr {
+					Instr::JumpIfTrue { .. } | Instr::JumpIfFalse { .. } => 1,
+					_ => usize::MAX,
+				};
+				for (n, arg) in instr.parts().1.into_iter().enumerate() {
+					match arg {
+						Arg::Position(value) => image[start + 1 + n] = relocate(value),
+						Arg::Immediate(value) if n == target => image[start + 1 + n] = relocate(value),
+						_ => (),
+					}
+				}
+				address += analysis.instrs[&address].advance();
+			},
+			Some(None) => address += analysis.instrs[&address].advance(),
+			None => {
+				if keep[address] {
+					image.push(program[address]);
+				}
+				address += 1;
+			},
+		}
+	}
+	image
+}
+
+//----------------------------------------------------------------
+
+/// Observable behaviour of a run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Behaviour {
+	pub output: Vec<i64>,
+	/// Halted normally, otherwise it crashed, ran out of input or out of budget.
+	pub halted: bool,
+}
+
+fn observe(program: &[i64], input: &[i64], budget: u64) -> (Behaviour, u64) {
+	let mut vm = Intcode::new(program);
+	vm.input.extend(input);
+	let mut output = Vec::new();
+	let halted = loop {
+		if vm.steps >= budget {
+			break false;
+		}
+		match vm.step() {
+			Ok(State::Running) => (),
+			Ok(State::Output(value)) => output.push(value),
+			Ok(State::Halted) => break true,
+			Ok(State::Input) | Err(_) => break false,
+		}
+	};
+	(Behaviour { output, halted }, vm.steps)
+}
+
+/// Result of validating the optimized program.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Validation {
+	pub runs: usize,
+	/// Instructions executed by the original program.
+	pub before: u64,
+	/// Instructions executed by the optimized program.
+	pub after: u64,
+}
+
+/// Runs both programs on every input and compares their behaviour.
+///
+/// Returns the first input for which the behaviour differs as an error.
+pub fn validate(original: &[i64], optimized: &[i64], inputs: &[Vec<i64>], budget: u64) -> Result<Validation, Vec<i64>> {
+	let mut validation = Validation::default();
+	for input in inputs {
+		let (expected, before) = observe(original, input, budget);
+		let (actual, after) = observe(optimized, input, budget);
+		if expected != actual {
+			return Err(input.clone());
+		}
+		validation.runs += 1;
+		validation.before += before;
+		validation.after += after;
+	}
+	Ok(validation)
+}
+
+#[test]
+fn test_rewrites() {
+	let program = [
+		1102,6,7,20,     // 0: mem[20] = 6 * 7
+		1002,20,1,22,    // 4: mem[22] = mem[20] * 1
+		1001,20,0,20,    // 8: mem[20] = mem[20] + 0
+		1105,0,99,       // 12: never taken
+		1105,1,24,       // 15: jump to 24 which jumps to the next instruction
+		99,              // 18: unreachable
+		-1,              // 19: unreferenced
+		0,1,0,           // 20: data
+		4,               // 23: unreachable
+		1106,0,27,       // 24: jump to 27, the next instruction
+		4,22,99,         // 27: output mem[22]
+	];
+	let (optimized, report) = optimize(&program);
+	assert!(report.analysis.is_static());
+	assert_eq!((report.folded, report.reduced, report.threaded, report.nops, report.unreachable), (1, 1, 1, 3, 4));
+	assert_eq!(optimized, [1101,42,0,11, 1001,11,0,12, 1105,1,13, 0,0, 4,12,99]);
+	let validation = validate(&program, &optimized, &[vec![]], 1000).unwrap();
+	assert_eq!((validation.before, validation.after), (7, 4));
+}
+
+#[test]
+fn test_unchanged() {
+	// Reads its own code
+	let program = [4,0,99];
+	let (optimized, report) = optimize(&program);
+	assert!(report.analysis.code_reads);
+	assert_eq!(optimized, program);
+	// Relative mode
+	let program = [109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99];
+	let (optimized, report) = optimize(&program);
+	assert!(report.analysis.relative);
+	assert_eq!(optimized, program);
+}
+
+#[test]
+fn test_in_place() {
+	let program = [
+		109,19,          // 0: relative mode, no cells can be removed
+		1102,6,7,21,     // 2: mem[21] = 6 * 7
+		204,2,           // 6: output mem[21]
+		1105,1,11,       // 8: jump to 11 which jumps to 14
+		1105,1,14,       // 11
+		1002,21,1,22,    // 14: mem[22] = mem[21] * 1
+		99,              // 18
+		0,0,0,0,         // 19: data
+	];
+	let (optimized, report) = optimize(&program);
+	assert!(report.analysis.relative);
+	assert_eq!((report.folded, report.reduced, report.threaded), (1, 1, 1));
+	assert_eq!(optimized, [109,19, 1101,42,0,21, 204,2, 1105,1,14, 1105,1,14, 1001,21,0,22, 99, 0,0,0,0]);
+	validate(&program, &optimized, &[vec![]], 1000).unwrap();
+
+	// The multiplication is read as data, it must stay as it is
+	let program = [1102,6,7,9, 4,1, 4,9, 99, 0];
+	let (optimized, report) = optimize(&program);
+	assert!(report.analysis.code_reads);
+	assert_eq!(optimized, program);
+}
+
+#[test]
+fn test_code_writes() {
+	let program = [
+		109,1,           // 0: relative mode
+		1102,6,7,13,     // 2: mem[13] = 6 * 7
+		21101,5,0,1,     // 6: mem[rb+1] = 5, overwrites the executed opcode at 2
+		4,13,            // 10: output mem[13]
+		99,              // 12
+		0,               // 13: data
+	];
+	let (optimized, report) = optimize(&program);
+	assert_eq!(report.folded, 1);
+	assert_ne!(optimized, program);
+	let (optimized, report) = optimize_checked(&program, &[vec![]], 1000);
+	assert_eq!(report.code_writes, [CodeWrite { address: 2, ip: 6, old: 1102, new: 5 }]);
+	assert_eq!(optimized, program);
+}
+
+#[test]
+fn test_day5_examples() {
+	let program = [3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99];
+	let (optimized, report) = optimize(&program);
+	assert!(report.analysis.is_static());
+	assert!(optimized.len() < program.len());
+	let inputs = (-2..12).map(|value| vec![value]).collect::<Vec<_>>();
+	let validation = validate(&program, &optimized, &inputs, 1000).unwrap();
+	assert!(validation.after < validation.before);
+}
@@ -0,0 +1,214 @@
+/*!
+Recording and replaying the input of Intcode sessions.
+
+A [`Session`](struct.Session.html) feeds the machine one input value at a time and records each value with the instruction count at which it was requested.
+Replaying the [`Log`](struct.Log.html) of a session reproduces it exactly: the machine is deterministic, so every value is requested at the same instruction count again.
+Once the log runs out the session can continue with live input.
+
+The log is a text file with one `steps value` pair per line:
+
+```text
+# steps value
+5 1
+873 -2
+```
+*/
+
+use std::collections::VecDeque;
+use std::fmt;
+use super::{Fault, Intcode, State};
+
+/// An input value and the instruction count at which it was requested.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+	pub steps: u64,
+	pub value: i64,
+}
+/// Formats the record as a line of the log, without the newline.
+impl fmt::Display for Record {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} {}", self.steps, self.value)
+	}
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Log {
+	pub records: Vec<Record>,
+}
+impl Log {
+	pub fn parse(text: &str) -> Result<Log, LogError> {
+		let mut records = Vec::new();
+		for (index, line) in text.lines().enumerate() {
+			let line = line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+			let mut fields = line.split_whitespace();
+			let record = match (fields.next(), fields.next(), fields.next()) {
+				(Some(steps), Some(value), None) => steps.parse().ok().and_then(|steps| value.parse().ok().map(|value| Record { steps, value })),
+				_ => None,
+			};
+			match record {
+				Some(record) => records.push(record),
+				None => return Err(LogError { line: index + 1, text: line.to_string() }),
+			}
+		}
+		Ok(Log { records })
+	}
+}
+impl fmt::Display for Log {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "# steps value")?;
+		for record in &self.records {
+			writeln!(f, "{}", record)?;
+		}
+		Ok(())
+	}
+}
+
+/// An invalid line in a log file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LogError {
+	pub line: usize,
+	pub text: String,
+}
+impl fmt::Display for LogError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid log entry on line {}: `{}`", self.line, self.text)
+	}
+}
+
+/// Why a session stopped.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Stop {
+	Halted,
+	/// The replayed log ran out and there is no live input.
+	LogEnd,
+	/// The live input ran out.
+	InputClosed,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SessionError {
+	Fault(Fault),
+	/// The machine requested input at a different instruction count than recorded, so the log belongs to another program or session.
+	Diverged { record: usize, expected: u64, actual: u64 },
+}
+impl From<Fault> for SessionError {
+	fn from(fault: Fault) -> SessionError {
+		SessionError::Fault(fault)
+	}
+}
+impl fmt::Display for SessionError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			SessionError::Fault(fault) => fault.fmt(f),
+			SessionError::Diverged { record, expected, actual } => write!(f, "replay diverged at record {}: input requested at step {} instead of {}", record, actual, expected),
+		}
+	}
+}
+
+type Live<'a> = Box<dyn FnMut() -> Option<i64> + 'a>;
+type Recorder<'a> = Box<dyn FnMut(&Record) + 'a>;
+
+/// Runs a machine, replaying and recording its input.
+pub struct Session<'a> {
+	pub vm: Intcode,
+	/// Every input consumed so far, replayed or live.
+	pub log: Log,
+	replay: VecDeque<Record>,
+	live: Option<Live<'a>>,
+	recorder: Option<Recorder<'a>>,
+}
+impl<'a> Session<'a> {
+	pub fn new(program: &[i64]) -> Session<'a> {
+		Session { vm: Intcode::new(program), log: Log::default(), replay: VecDeque::new(), live: None, recorder: None }
+	}
+	/// Replays the log before any live input.
+	pub fn replay(mut self, log: &Log) -> Session<'a> {
+		self.replay.extend(&log.records);
+		self
+	}
+	/// Reads live input once the replayed log runs out.
+	pub fn live<F: FnMut() -> Option<i64> + 'a>(mut self, input: F) -> Session<'a> {
+		self.live = Some(Box::new(input));
+		self
+	}
+	/// Passes every record to `recorder` as soon as its input is consumed, eg. to append it to a log file.
+	pub fn recorder<F: FnMut(&Record) + 'a>(mut self, recorder: F) -> Session<'a> {
+		self.recorder = Some(Box::new(recorder));
+		self
+	}
+	/// Runs until the machine halts or runs out of input, passing every output to `output`.
+	pub fn run(&mut self, output: &mut dyn FnMut(i64)) -> Result<Stop, SessionError> {
+		loop {
+			match self.vm.run()? {
+				State::Running => (),
+				State::Output(value) => output(value),
+				State::Halted => return Ok(Stop::Halted),
+				State::Input => {
+					let record = match self.replay.pop_front() {
+						Some(record) if record.steps != self.vm.steps => {
+							let record_index = self.log.records.len();
+							return Err(SessionError::Diverged { record: record_index, expected: record.steps, actual: self.vm.steps });
+						},
+						Some(record) => record,
+						None => {
+							let live = match &mut self.live {
+								Some(live) => live,
+								None => return Ok(Stop::LogEnd),
+							};
+							match live() {
+								Some(value) => Record { steps: self.vm.steps, value },
+								None => return Ok(Stop::InputClosed),
+							}
+						},
+					};
+					self.vm.input.push_back(record.value);
+					if let Some(recorder) = &mut self.recorder {
+						recorder(&record);
+					}
+					self.log.records.push(record);
+				},
+			}
+		}
+	}
+}
+
+#[test]
+fn test_record_replay() {
+	// Sums its inputs until it reads zero
+	let program = [1101,0,0,20,3,21,1006,21,16,1,20,21,20,1105,1,4,4,20,99];
+	let mut output = Vec::new();
+
+	let mut live = vec![7, 0, 5];
+	let mut session = Session::new(&program).live(move || live.pop());
+	assert_eq!(session.run(&mut |value| output.push(value)), Ok(Stop::Halted));
+	let log = session.log.clone();
+	assert_eq!(log.records, [Record { steps: 1, value: 5 }, Record { steps: 5, value: 0 }]);
+	assert_eq!(Log::parse(&log.to_string()), Ok(log.clone()));
+
+	let mut session = Session::new(&program).replay(&log);
+	assert_eq!(session.run(&mut |value| output.push(value)), Ok(Stop::Halted));
+	assert_eq!(output, [5, 5]);
+
+	// Stop where the log ends or switch to live input
+	let partial = Log { records: log.records[..1].to_vec() };
+	let mut session = Session::new(&program).replay(&partial);
+	assert_eq!(session.run(&mut |_| ()), Ok(Stop::LogEnd));
+	assert_eq!(session.vm.steps, 5);
+	let mut recorded = Vec::new();
+	let mut session = Session::new(&program).replay(&partial).live(|| Some(0)).recorder(|record| recorded.push(*record));
+	assert_eq!(session.run(&mut |value| assert_eq!(value, 5)), Ok(Stop::Halted));
+	assert_eq!(session.log, log);
+	drop(session);
+	assert_eq!(recorded, log.records);
+}
+
+#[test]
+fn test_diverged() {
+	let log = Log::parse("# steps value\n0 1\n 2 5 \n").unwrap();
+	let mut session = Session::new(&[3,0,3,0,99]).replay(&log);
+	assert_eq!(session.run(&mut |_| ()), Err(SessionError::Diverged { record: 1, expected: 2, actual: 1 }));
+	assert_eq!(Log::parse("1 2\n3"), Err(LogError { line: 2, text: "3".into() }));
+}
@@ -0,0 +1,88 @@
+/*!
+Self-modifying code detection.
+
+When enabled with [`Intcode::track_code`](../struct.Intcode.html#method.track_code) the machine remembers which cells were executed as part of an instruction (the opcode and its parameters)
+and records every write into those cells.
+*/
+
+use std::fmt;
+
+/// A write into a cell which was previously executed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CodeWrite {
+	/// The address written to.
+	pub address: usize,
+	/// The instruction which did the write.
+	pub ip: usize,
+	pub old: i64,
+	pub new: i64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CodeTracker {
+	executed: Vec<bool>,
+	pub writes: Vec<CodeWrite>,
+}
+impl CodeTracker {
+	/// Returns if the cell was executed as part of an instruction.
+	pub fn is_code(&self, address: usize) -> bool {
+		self.executed.get(address).cloned().unwrap_or(false)
+	}
+	/// Returns if any executed cell was written to.
+	pub fn modified(&self) -> bool {
+		!self.writes.is_empty()
+	}
+	pub(super) fn execute(&mut self, ip: usize, len: usize) {
+		if ip + len > self.executed.len() {
+			self.executed.resize(ip + len, false);
+		}
+		for cell in &mut self.executed[ip..ip + len] {
+			*cell = true;
+		}
+	}
+	pub(super) fn write(&mut self, ip: usize, address: usize, old: i64, new: i64) {
+		if self.is_code(address) {
+			self.writes.push(CodeWrite { address, ip, old, new });
+		}
+	}
+}
+impl fmt::Display for CodeTracker {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let code = self.executed.iter().filter(|&&executed| executed).count();
+		writeln!(f, "Executed `{}` cells as code, `{}` writes into code.", code, self.writes.len())?;
+		for write in &self.writes {
+			writeln!(f, "  address {:>5} written by ip {:>5}: `{}` -> `{}`", write.address, write.ip, write.old, write.new)?;
+		}
+		Ok(())
+	}
+}
+
+#[test]
+fn test_patch_self() {
+	use super::{Intcode, State};
+
+	// Writes into its opcode after executing it
+	let mut vm = Intcode::new(&[1,0,0,0,99]);
+	vm.track_code();
+	assert_eq!(vm.run(), Ok(State::Halted));
+	let tracker = vm.smc.as_ref().unwrap();
+	assert_eq!(tracker.writes, [CodeWrite { address: 0, ip: 0, old: 1, new: 2 }]);
+
+	// Patches the next instruction before it is executed, then writes into the first one
+	let mut vm = Intcode::new(&[1,1,1,4,99,5,6,0,99]);
+	vm.track_code();
+	assert_eq!(vm.run(), Ok(State::Halted));
+	let tracker = vm.smc.as_ref().unwrap();
+	assert_eq!(tracker.writes, [CodeWrite { address: 0, ip: 4, old: 1, new: 30 }]);
+	assert!(tracker.is_code(4) && tracker.is_code(8));
+}
+
+#[test]
+fn test_data_writes() {
+	use super::{Intcode, State};
+
+	let mut vm = Intcode::new(&[1101,1,2,5,99,0]);
+	vm.track_code();
+	assert_eq!(vm.run(), Ok(State::Halted));
+	assert!(!vm.smc.as_ref().unwrap().modified());
+}
@@ -0,0 +1,10 @@
+/*!
+Shared code for the Advent of Code 2019 solutions.
+*/
+
+pub mod automaton;
+pub mod days;
+pub mod grid;
+pub mod intcode;
+pub mod ocr;
+pub mod solution;
@@ -0,0 +1,57 @@
+/*!
+Reads the capital letters some puzzles draw as their answer.
+
+The letters are 4 pixels wide and 6 pixels high with a blank column between them, `#` is a lit pixel.
+*/
+
+/// The known letters with their rows from top to bottom.
+const GLYPHS: [(char, [&str; 6]); 16] = [
+	('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+	('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+	('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+	('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+	('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+	('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+	('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+	('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+	('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+	('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+	('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+	('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+	('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+	('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+	('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+	('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Reads the letters in the image, `None` unless it's six rows of known letters.
+pub fn read(image: &str) -> Option<String> {
+	let rows = image.lines().filter(|line| !line.trim().is_empty()).map(|line| line.as_bytes()).collect::<Vec<_>>();
+	if rows.len() != 6 {
+		return None;
+	}
+	let width = rows.iter().map(|row| row.len()).max()?;
+	let lit = |x: usize, y: usize| rows[y].get(x) == Some(&b'#');
+	(0..width.div_ceil(5)).map(|index| {
+		GLYPHS.iter().find(|(_, glyph)| {
+			glyph.iter().enumerate().all(|(y, row)| row.bytes().enumerate().all(|(dx, pixel)| lit(index * 5 + dx, y) == (pixel == b'#')))
+		}).map(|&(letter, _)| letter)
+	}).collect()
+}
+
+#[test]
+fn test_read() {
+	let image = "\
+#### ###  #  #  ##  ###
+#    #  # #  # #  # #  #
+###  #  # #  # #  # #  #
+#    ###  #  # #### ###
+#    #    #  # #  # # #
+#    #     ##  #  # #  #
+";
+	assert_eq!(read(image), Some("FPUAR".to_string()));
+	let image = "\n  ## #  #  ##  ###  ###   ##   ##  #  #\n   # #  # #  # #  # #  # #  # #  # #  #\n   # #### #  # #  # ###  #    #    #  #\n   # #  # #### ###  #  # # ## #    #  #\n#  # #  # #  # # #  #  # #  # #  # #  #\n ##  #  # #  # #  # ###   ###  ##   ## \n";
+	assert_eq!(read(image), Some("JHARBGCU".to_string()));
+	assert_eq!(read("#\n#\n#\n#\n#\n#\n"), None);
+	assert_eq!(read("12345"), None);
+}
@@ -0,0 +1,107 @@
+/*!
+Common interface of the daily solutions, used by the `aoc` runner.
+
+Every day implements [`Solution`] on a unit struct and is registered with [`Entry::of`].
+*/
+
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+use crate::ocr;
+
+pub trait Solution {
+	/// The day of the puzzle.
+	const DAY: u32;
+	/// Answer to part one given the puzzle input.
+	fn part_one(input: &str) -> String;
+	/// Answer to part two given the puzzle input, `None` when there's no solution for it.
+	fn part_two(_input: &str) -> Option<String> {
+		None
+	}
+}
+
+/// A registered solution with its parts as plain functions.
+#[derive(Copy, Clone)]
+pub struct Entry {
+	pub day: u32,
+	pub parts: [fn(&str) -> Option<String>; 2],
+}
+impl Entry {
+	pub fn of<S: Solution>() -> Entry {
+		Entry { day: S::DAY, parts: [|input| Some(S::part_one(input)), S::part_two] }
+	}
+	/// Solves the part, numbered from one, and measures how long it took.
+	pub fn solve(&self, part: usize, input: &str) -> Record {
+		let start = Instant::now();
+		let answer = (self.parts[part - 1])(input);
+		Record { day: self.day, part, answer, duration: start.elapsed() }
+	}
+}
+
+/// The answer to a part and how long it took.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+	pub day: u32,
+	pub part: usize,
+	pub answer: Option<String>,
+	pub duration: Duration,
+}
+impl Record {
+	/// The letters drawn by an image answer.
+	pub fn ocr(&self) -> Option<String> {
+		self.answer.as_deref().filter(|answer| answer.contains('\n')).and_then(ocr::read)
+	}
+	/// Formats the record as a JSON object on a single line, image answers are read into an `ocr` field.
+	pub fn to_json(&self) -> String {
+		let mut json = format!("{{\"day\":{},\"part\":{},\"answer\":", self.day, self.part);
+		match &self.answer {
+			Some(answer) => json_string(&mut json, answer),
+			None => json.push_str("null"),
+		}
+		if let Some(text) = self.ocr() {
+			json.push_str(",\"ocr\":");
+			json_string(&mut json, &text);
+		}
+		let _ = write!(json, ",\"duration_ns\":{}}}", self.duration.as_nanos());
+		json
+	}
+}
+
+fn json_string(json: &mut String, s: &str) {
+	json.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => json.push_str("\\\""),
+			'\\' => json.push_str("\\\\"),
+			'\n' => json.push_str("\\n"),
+			c if (c as u32) < 0x20 => { let _ = write!(json, "\\u{:04x}", c as u32); },
+			c => json.push(c),
+		}
+	}
+	json.push('"');
+}
+
+#[test]
+fn test_entry() {
+	struct Sum;
+	impl Solution for Sum {
+		const DAY: u32 = 1;
+		fn part_one(input: &str) -> String {
+			input.split_whitespace().map(|n| n.parse::<i32>().unwrap()).sum::<i32>().to_string()
+		}
+	}
+	let entry = Entry::of::<Sum>();
+	assert_eq!(entry.day, 1);
+	assert_eq!(entry.solve(1, "1 2 3").answer, Some("6".to_string()));
+	assert_eq!(entry.solve(2, "1 2 3").answer, None);
+}
+
+#[test]
+fn test_json() {
+	let record = Record { day: 7, part: 1, answer: Some("say \"hi\"\\\t".to_string()), duration: Duration::from_nanos(1500) };
+	assert_eq!(record.to_json(), r#"{"day":7,"part":1,"answer":"say \"hi\"\\\u0009","duration_ns":1500}"#);
+	let record = Record { day: 10, part: 2, answer: None, duration: Duration::from_nanos(3) };
+	assert_eq!(record.to_json(), r#"{"day":10,"part":2,"answer":null,"duration_ns":3}"#);
+	let image = ".##.\n#..#\n#..#\n####\n#..#\n#..#\n";
+	let record = Record { day: 8, part: 2, answer: Some(image.to_string()), duration: Duration::from_nanos(0) };
+	assert_eq!(record.to_json(), r#"{"day":8,"part":2,"answer":".##.\n#..#\n#..#\n####\n#..#\n#..#\n","ocr":"A","duration_ns":0}"#);
+}